@@ -5,10 +5,10 @@
 //! Currently implemented are the:
 //! - [`LinkedList`], a singly linked list.
 //! - [`Stack`], a LIFO stack.
-//! - [`PriorityQueue`], queue with in order insertion.
+//! - [`PriorityQueue`], queue ordered by a binary heap.
 //!
 //! [`LinkedList`]: ./linkedlist/struct.LinkedList.html
-//! [`Stack`]: ./linkedlist/type.Stack.html
+//! [`Stack`]: ./linkedlist/struct.Stack.html
 //! [`PriorityQueue`]: ./queues/struct.PriorityQueue.html
 
 /// Module for the LinkedList.
@@ -18,8 +18,8 @@
 /// an interator. Such a linked list is best used as a stack, where the only
 /// interaction is done at the head of the list.
 ///
-/// In this module is also an alias for a Stack, which is just a linked list but only uses pop and
-/// push for interaction.
+/// In this module is also a Stack, which wraps a linked list but only allows pop, push and
+/// peek for interaction.
 #[allow(dead_code)]
 pub mod linkedlist;
 
@@ -1,11 +1,7 @@
 /// Singly linked list.
-#[derive(Clone)]
 pub struct LinkedList<T>(pub(super) Option<(T, Box<LinkedList<T>>)>);
 
-impl<T> LinkedList<T>
-where
-    T: Copy,
-{
+impl<T> LinkedList<T> {
     pub fn new() -> Self {
         Self(None)
     }
@@ -15,14 +11,11 @@ where
     /// ```
     /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2]);
     /// ll.append(3);
-    /// let list: Vec<i32> = ll.collect();
+    /// let list: Vec<i32> = ll.into_iter().collect();
     /// assert_eq!(list, vec![1, 2, 3]);
     /// ```
     pub fn append(&mut self, data: T) {
-        match self.0 {
-            Some(ref mut ll) => ll.1.append(data),
-            None => self.0 = Some((data, Box::new(LinkedList::new()))),
-        };
+        self.tail_mut().0 = Some((data, Box::new(LinkedList::new())));
     }
 
     /// Add data to the front of the list
@@ -30,7 +23,7 @@ where
     /// ```
     /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2]);
     /// ll.push(3);
-    /// let list: Vec<i32> = ll.collect();
+    /// let list: Vec<i32> = ll.into_iter().collect();
     /// assert_eq!(list, vec![3, 1, 2]);
     /// ```
     pub fn push(&mut self, data: T) {
@@ -50,14 +43,9 @@ where
     /// assert_eq!(ll.pop(), None);
     /// ```
     pub fn pop(&mut self) -> Option<T> {
-        let (data, child) = self.0.as_mut()?; // borrow the current value in self
-        let data = *data; // copy the data value
-
-        // magic
-        let mut dummy = Box::new(LinkedList::new());
-        std::mem::swap(child, &mut dummy);
-        *self = *dummy;
-
+        // move the first node out of self, and replace it by its child
+        let (data, mut child) = self.0.take()?;
+        self.0 = child.0.take();
         Some(data)
     }
 
@@ -67,14 +55,47 @@ where
     ///
     /// ```
     /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2]);
-    /// assert_eq!(ll.peek(), Some(1));
-    /// assert_eq!(ll.peek(), Some(1));
+    /// assert_eq!(ll.peek(), Some(&1));
+    /// assert_eq!(ll.peek(), Some(&1));
     ///
     /// let ll = data_structures::linkedlist::LinkedList::<i32>::new();
     /// assert_eq!(ll.peek(), None);
     /// ```
-    pub fn peek(&self) -> Option<T> {
-        Some(self.0.as_ref()?.0)
+    pub fn peek(&self) -> Option<&T> {
+        self.0.as_ref().map(|(data, _)| data)
+    }
+
+    /// Inspect the last value in the list without removing it
+    ///
+    /// When the list is empty, None is returned.
+    ///
+    /// ```
+    /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2]);
+    /// assert_eq!(ll.last(), Some(&2));
+    /// ll.append(3);
+    /// assert_eq!(ll.last(), Some(&3));
+    /// ```
+    pub fn last(&self) -> Option<&T> {
+        self.iter().last()
+    }
+
+    /// Get a mutable reference to the last value in the list
+    ///
+    /// When the list is empty, None is returned.
+    ///
+    /// ```
+    /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2]);
+    /// if let Some(data) = ll.last_mut() {
+    ///     *data = 5;
+    /// }
+    /// assert_eq!(ll.last(), Some(&5));
+    /// ```
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        let mut cur = self;
+        while cur.0.as_ref()?.1 .0.is_some() {
+            cur = &mut cur.0.as_mut().unwrap().1;
+        }
+        cur.0.as_mut().map(|(data, _)| data)
     }
 
     /// Insert data at specific index in the list
@@ -86,27 +107,116 @@ where
     ///
     ///
     /// ll.insert(2, 1); // insert number 2 at index 1
-    /// let list: Vec<i32> = ll.clone().collect();
+    /// let list: Vec<i32> = ll.clone().into_iter().collect();
     /// assert_eq!(list, vec![1, 2, 3]);
     ///
     /// ll.insert(-1, 0); // insert number -1 at the beginning
-    /// let list: Vec<i32> = ll.clone().collect();
+    /// let list: Vec<i32> = ll.clone().into_iter().collect();
     /// assert_eq!(list, vec![-1, 1, 2, 3]);
     ///
     /// ll.insert(5, 99); // insert number -1 at the end (99 is out of range)
-    /// let list: Vec<i32> = ll.clone().collect();
+    /// let list: Vec<i32> = ll.clone().into_iter().collect();
     /// assert_eq!(list, vec![-1, 1, 2, 3, 5]);
     /// ```
     pub fn insert(&mut self, data: T, n: usize) {
-        match self.0 {
-            None => self.append(data),
-            Some((_, ref mut child)) => {
-                if n > 0 {
-                    child.insert(data, n - 1);
-                } else {
-                    self.insert_here(data)
+        self.sublist_mut(n).insert_here(data)
+    }
+
+    /// Insert all items of `iter` at index `index`, keeping their order
+    ///
+    /// The items are collected into a list first, which is then linked in
+    /// as a whole. When the index is out of range, the items are added at
+    /// the end.
+    ///
+    /// ```
+    /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 4]);
+    /// ll.splice_iter(1, vec![2, 3]);
+    /// let list: Vec<i32> = ll.into_iter().collect();
+    /// assert_eq!(list, vec![1, 2, 3, 4]);
+    /// ```
+    pub fn splice_iter<I>(&mut self, index: usize, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let at = self.sublist_mut(index);
+        let rest = at.0.take();
+        at.0 = LinkedList::from_iter(iter).0.take();
+        at.tail_mut().0 = rest;
+    }
+
+    /// Remove the value at index `n` from the list
+    ///
+    /// Unlike [`insert`](LinkedList::insert), an out of range index does not
+    /// fall back to the end of the list: nothing is removed, and `None` is
+    /// returned.
+    ///
+    /// ```
+    /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3]);
+    /// assert_eq!(ll.remove(1), Some(2));
+    /// assert_eq!(ll.remove(2), None);
+    /// let list: Vec<i32> = ll.into_iter().collect();
+    /// assert_eq!(list, vec![1, 3]);
+    /// ```
+    pub fn remove(&mut self, n: usize) -> Option<T> {
+        // an out of range index ends up at the empty end, which pops nothing
+        self.sublist_mut(n).pop()
+    }
+
+    /// Keep only the items for which `f` holds, dropping the others in place
+    ///
+    /// The list is relinked around the dropped items, without allocating.
+    ///
+    /// ```
+    /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3, 4]);
+    /// ll.retain(|x| x % 2 == 0);
+    /// let list: Vec<i32> = ll.into_iter().collect();
+    /// assert_eq!(list, vec![2, 4]);
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut cur = self;
+        while let Some((ref data, _)) = cur.0 {
+            if f(data) {
+                cur = &mut cur.0.as_mut().unwrap().1;
+            } else {
+                cur.pop();
+            }
+        }
+    }
+
+    /// Insert all items from an iterator into a sorted list, keeping it sorted
+    ///
+    /// The incoming items are sorted first, after which they are merged into
+    /// the list in a single pass, instead of searching from the head for every
+    /// item. Items without an order, like `NaN`, do not cause a panic, but
+    /// end up at an unspecified place.
+    ///
+    /// ```
+    /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![2, 4]);
+    /// ll.insert_all_sorted(vec![5, 1, 3]);
+    /// let list: Vec<i32> = ll.into_iter().collect();
+    /// assert_eq!(list, vec![1, 2, 3, 4, 5]);
+    /// ```
+    pub fn insert_all_sorted<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+        T: PartialOrd,
+    {
+        let batch = merge_sort(iter.into_iter().collect());
+
+        let mut cur = self;
+        for data in batch {
+            // skip past all items which should come before data
+            while let Some((ref it, _)) = cur.0 {
+                if data < *it {
+                    break;
                 }
+                cur = &mut cur.0.as_mut().unwrap().1;
             }
+            cur.insert_here(data);
+            cur = &mut cur.0.as_mut().unwrap().1;
         }
     }
 
@@ -116,156 +226,2148 @@ where
         new.append(data);
 
         std::mem::swap(self, &mut new);
-        let mut child = self.0.as_mut().unwrap();
-        child.1 = Box::new(new)
+        let child = self.0.as_mut().unwrap();
+        *child.1 = new
+    }
+
+    /// Iterate over references to the values in the list
+    ///
+    /// Unlike iterating over the list itself, this does not consume the list.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2]);
+    /// let list: Vec<&i32> = ll.iter().collect();
+    /// assert_eq!(list, vec![&1, &2]);
+    /// assert_eq!(ll.peek(), Some(&1));
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter(self)
     }
 
-    fn from_helper<I>(&mut self, iter: &mut I)
+    /// Iterate over mutable references to the values in the list
+    ///
+    /// ```
+    /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2]);
+    /// for data in ll.iter_mut() {
+    ///     *data *= 10;
+    /// }
+    /// let list: Vec<i32> = ll.into_iter().collect();
+    /// assert_eq!(list, vec![10, 20]);
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut(Some(self))
+    }
+
+    /// Iterate over references to the values in the list, with their index
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![5, 6]);
+    /// let list: Vec<(usize, &i32)> = ll.iter_indexed().collect();
+    /// assert_eq!(list, vec![(0, &5), (1, &6)]);
+    /// ```
+    pub fn iter_indexed(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.iter().enumerate()
+    }
+
+    /// Create a list by calling `f` until it returns None
+    ///
+    /// Each produced value is appended to the end of the list. This mirrors
+    /// [`std::iter::from_fn`].
+    ///
+    /// ```
+    /// let mut count = 0;
+    /// let ll = data_structures::linkedlist::LinkedList::from_fn(|| {
+    ///     count += 1;
+    ///     if count < 4 { Some(count) } else { None }
+    /// });
+    /// let list: Vec<i32> = ll.into_iter().collect();
+    /// assert_eq!(list, vec![1, 2, 3]);
+    /// ```
+    pub fn from_fn<F>(mut f: F) -> Self
     where
-        I: Iterator<Item = T>,
+        F: FnMut() -> Option<T>,
     {
-        match iter.next() {
-            None => return,
-            Some(item) => {
-                self.append(item);
-                self.0.as_mut().unwrap().1.from_helper(iter);
-            }
-        };
+        let mut ll = LinkedList::new();
+        let mut tail = &mut ll;
+        while let Some(data) = f() {
+            tail.0 = Some((data, Box::new(LinkedList::new())));
+            tail = &mut tail.0.as_mut().unwrap().1;
+        }
+        ll
     }
-}
 
-impl<T> FromIterator<T> for LinkedList<T>
-where
-    T: Copy,
-{
-    fn from_iter<I>(list: I) -> Self
+    /// Create a list from an iterator, in reverse order
+    ///
+    /// Each item is added to the front of the list, so the last item of the
+    /// iterator becomes the head.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter_rev(vec![1, 2, 3]);
+    /// let list: Vec<i32> = ll.into_iter().collect();
+    /// assert_eq!(list, vec![3, 2, 1]);
+    /// ```
+    pub fn from_iter_rev<I>(iter: I) -> Self
     where
-        I: std::iter::IntoIterator<Item = T>,
+        I: IntoIterator<Item = T>,
     {
         let mut ll = LinkedList::new();
-        ll.from_helper(&mut list.into_iter());
+        for data in iter {
+            let rest = LinkedList(ll.0.take());
+            ll.0 = Some((data, Box::new(rest)));
+        }
         ll
     }
-}
 
-impl<T> Iterator for LinkedList<T>
-where
-    T: Copy,
-{
-    type Item = T;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let (data, child) = self.0.as_mut()?;
-        let data = *data;
-        let mut dummy = Box::new(LinkedList::new());
-        std::mem::swap(child, &mut dummy);
-        *self = *dummy;
-        Some(data)
+    /// Consume the list and return it in reverse order
+    ///
+    /// The nodes are moved one by one from the head of the list onto the head
+    /// of the new list, so no values are copied.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3]);
+    /// let list: Vec<i32> = ll.into_reversed().into_iter().collect();
+    /// assert_eq!(list, vec![3, 2, 1]);
+    /// ```
+    pub fn into_reversed(mut self) -> LinkedList<T> {
+        let mut reversed = LinkedList::new();
+        while let Some((data, mut child)) = self.0.take() {
+            self.0 = child.0.take();
+            child.0 = reversed.0.take();
+            reversed.0 = Some((data, child));
+        }
+        reversed
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[test]
-    fn create_ll_test() {
-        let ll: LinkedList<i32> = LinkedList::new();
-        assert!(ll.0.is_none())
+    /// Reverse the order of the list in place
+    ///
+    /// Like [`into_reversed`](LinkedList::into_reversed), the existing nodes
+    /// are relinked, so nothing is allocated or copied.
+    ///
+    /// ```
+    /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3]);
+    /// ll.reverse();
+    /// let list: Vec<i32> = ll.into_iter().collect();
+    /// assert_eq!(list, vec![3, 2, 1]);
+    /// ```
+    pub fn reverse(&mut self) {
+        *self = std::mem::take(self).into_reversed();
     }
 
-    #[test]
-    fn append_ll_int_test() {
-        let mut ll = LinkedList(None);
-        ll.append(1);
-        assert!(ll.0.is_some());
-        let child = ll.0.unwrap();
-        assert_eq!(child.0, 1);
-        assert!(child.1 .0.is_none());
+    /// Consume the list, splitting it into the items at even and odd indices
+    ///
+    /// The order within both lists is preserved, and the nodes are moved.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3, 4, 5]);
+    /// let (even, odd) = ll.split_parity();
+    /// assert_eq!(even.into_iter().collect::<Vec<i32>>(), vec![1, 3, 5]);
+    /// assert_eq!(odd.into_iter().collect::<Vec<i32>>(), vec![2, 4]);
+    /// ```
+    pub fn split_parity(mut self) -> (LinkedList<T>, LinkedList<T>) {
+        let mut even = LinkedList::new();
+        let mut odd = LinkedList::new();
+        let mut tail = &mut even;
+        let mut other_tail = &mut odd;
+        while let Some((data, mut node)) = self.0.take() {
+            self.0 = node.0.take();
+            tail.0 = Some((data, node));
+            tail = &mut tail.0.as_mut().unwrap().1;
+            // the next item goes into the other list
+            std::mem::swap(&mut tail, &mut other_tail);
+        }
+        (even, odd)
     }
 
-    #[test]
-    fn append_ll_str_test() {
-        let mut ll = LinkedList(None);
-        ll.append("abc");
-        assert!(ll.0.is_some());
-        let child = ll.0.unwrap();
-        assert_eq!(child.0, "abc");
-        assert!(child.1 .0.is_none());
+    /// Consume the list, combining the values from left to right with `f`
+    ///
+    /// Returns None for an empty list, and the only value for a list with a
+    /// single item.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3, 4]);
+    /// assert_eq!(ll.reduce(|a, b| a + b), Some(10));
+    /// ```
+    pub fn reduce<F>(mut self, mut f: F) -> Option<T>
+    where
+        F: FnMut(T, T) -> T,
+    {
+        let mut result = self.pop()?;
+        while let Some(data) = self.pop() {
+            result = f(result, data);
+        }
+        Some(result)
     }
 
-    #[test]
-    fn pop_test() {
-        let mut ll = LinkedList::new();
-        ll.push(1);
-        ll.push(2);
-
-        assert_eq!(ll.pop(), Some(2));
-        assert_eq!(ll.pop(), Some(1));
-        assert_eq!(ll.pop(), None);
-        assert_eq!(ll.pop(), None);
-
-        ll.append(5);
-        assert_eq!(ll.pop(), Some(5));
+    /// Consume both lists, pairing up their items up to the longest length
+    ///
+    /// When one list runs out, clones of `fill` take the place of its items.
+    ///
+    /// ```
+    /// use data_structures::linkedlist::LinkedList;
+    ///
+    /// let ll = LinkedList::from_iter(vec![1, 2, 3]);
+    /// let pairs: Vec<(i32, i32)> = ll
+    ///     .zip_longest(LinkedList::from_iter(vec![9]), 0)
+    ///     .into_iter()
+    ///     .collect();
+    /// assert_eq!(pairs, vec![(1, 9), (2, 0), (3, 0)]);
+    /// ```
+    pub fn zip_longest(mut self, mut other: LinkedList<T>, fill: T) -> LinkedList<(T, T)>
+    where
+        T: Clone,
+    {
+        LinkedList::from_fn(|| match (self.pop(), other.pop()) {
+            (None, None) => None,
+            (a, b) => Some((
+                a.unwrap_or_else(|| fill.clone()),
+                b.unwrap_or_else(|| fill.clone()),
+            )),
+        })
     }
 
-    #[test]
-    fn peek_test() {
-        let mut ll = LinkedList::new();
-        assert_eq!(ll.peek(), None);
-        ll.push(0);
-        assert_eq!(ll.peek(), Some(0));
+    /// Consume the list, splitting it wherever `boundary` holds for neighbours
+    ///
+    /// A new list is started before every item `curr` for which
+    /// `boundary(prev, curr)` returns true, where `prev` is the item before
+    /// it. The nodes are moved into the new lists.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 5]);
+    /// let lists = ll.split_when(|prev, curr| curr - prev > 2);
+    /// assert_eq!(lists.len(), 2);
+    /// ```
+    pub fn split_when<F>(mut self, mut boundary: F) -> Vec<LinkedList<T>>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut lists = Vec::new();
+        while self.0.is_some() {
+            // find the length of the run at the head of the list
+            let mut values = self.iter();
+            let mut prev = values.next().unwrap();
+            let mut len = 1;
+            for curr in values {
+                if boundary(prev, curr) {
+                    break;
+                }
+                prev = curr;
+                len += 1;
+            }
+            lists.push(self.take(len));
+        }
+        lists
     }
 
-    #[test]
-    fn insert_test() {
-        let mut ll = LinkedList::new();
-
-        ll.push(3);
-        ll.push(1);
-        ll.insert(2, 1);
+    /// Merge two sorted lists into one sorted list without duplicates
+    ///
+    /// This is the set union of both lists. The nodes are relinked, so this
+    /// takes linear time.
+    ///
+    /// ```
+    /// use data_structures::linkedlist::LinkedList;
+    ///
+    /// let a = LinkedList::from_iter(vec![1, 2, 4]);
+    /// let b = LinkedList::from_iter(vec![2, 3, 4]);
+    /// let list: Vec<i32> = a.union_sorted(b).into_iter().collect();
+    /// assert_eq!(list, vec![1, 2, 3, 4]);
+    /// ```
+    pub fn union_sorted(mut self, mut other: LinkedList<T>) -> LinkedList<T>
+    where
+        T: Ord,
+    {
+        let mut result = LinkedList::new();
+        let mut tail = &mut result;
+        loop {
+            // take from the list with the smallest head
+            let source = match (&self.0, &other.0) {
+                (None, None) => break,
+                (Some(_), None) => &mut self,
+                (None, Some(_)) => &mut other,
+                (Some((a, _)), Some((b, _))) => {
+                    if a <= b {
+                        &mut self
+                    } else {
+                        &mut other
+                    }
+                }
+            };
+            let (data, mut node) = source.0.take().unwrap();
+            source.0 = node.0.take();
 
-        assert_eq!(ll.pop(), Some(1));
-        assert_eq!(ll.pop(), Some(2));
-        assert_eq!(ll.pop(), Some(3));
+            self.drop_leading(&data);
+            other.drop_leading(&data);
+            tail.0 = Some((data, node));
+            tail = &mut tail.0.as_mut().unwrap().1;
+        }
+        result
+    }
 
-        let mut ll = LinkedList::new();
-        ll.insert(2, 0);
-        assert_eq!(ll.pop(), Some(2));
+    /// Consume the list, and concatenate the results of `f` on each item
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2]);
+    /// let list: Vec<i32> = ll.flat_map(|x| vec![x, x * 10]).into_iter().collect();
+    /// assert_eq!(list, vec![1, 10, 2, 20]);
+    /// ```
+    pub fn flat_map<U, I, F>(mut self, mut f: F) -> LinkedList<U>
+    where
+        I: IntoIterator<Item = U>,
+        F: FnMut(T) -> I,
+    {
+        let mut result = LinkedList::new();
+        let mut tail = &mut result;
+        while let Some(data) = self.pop() {
+            for item in f(data) {
+                tail.0 = Some((item, Box::new(LinkedList::new())));
+                tail = &mut tail.0.as_mut().unwrap().1;
+            }
+        }
+        result
     }
 
-    #[test]
-    fn from_test() {
-        let lst = vec![1, 2, 3, 4];
-        let mut ll = LinkedList::from_iter(lst);
-        assert_eq!(ll.pop(), Some(1));
-        assert_eq!(ll.pop(), Some(2));
-        assert_eq!(ll.pop(), Some(3));
-        assert_eq!(ll.pop(), Some(4));
+    /// Consume the list, merging neighbouring items with `f`
+    ///
+    /// Whenever `f(prev, next)` returns `Some(merged)`, the pair is replaced by
+    /// the merged item, which is then compared with the item after it. When
+    /// `f` returns `None`, both items are kept.
+    ///
+    /// ```
+    /// // sum runs of equal numbers, keeping the number next to its sum
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 1, 2, 3, 3])
+    ///     .flat_map(|x| Some((x, x)));
+    /// let sums: Vec<i32> = ll
+    ///     .coalesce(|a, b| if a.0 == b.0 { Some((a.0, a.1 + b.1)) } else { None })
+    ///     .into_iter().map(|(_, sum)| sum)
+    ///     .collect();
+    /// assert_eq!(sums, vec![2, 2, 6]);
+    /// ```
+    pub fn coalesce<F>(mut self, mut f: F) -> LinkedList<T>
+    where
+        F: FnMut(&T, &T) -> Option<T>,
+    {
+        let mut pending = self.pop();
+        LinkedList::from_fn(|| {
+            let mut acc = pending.take()?;
+            while let Some(next) = self.pop() {
+                match f(&acc, &next) {
+                    Some(merged) => acc = merged,
+                    None => {
+                        pending = Some(next);
+                        break;
+                    }
+                }
+            }
+            Some(acc)
+        })
     }
 
-    #[test]
-    fn from_empty_test() {
-        let lst: Vec<i32> = Vec::new();
-        let mut ll = LinkedList::from_iter(lst);
-        assert_eq!(ll.pop(), None);
+    /// Check if `value` is in the list
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3]);
+    /// assert!(ll.contains(&2));
+    /// assert!(!ll.contains(&4));
+    /// ```
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().any(|data| data == value)
     }
 
-    #[test]
-    fn from_map_test() {
-        let lst = vec![1, 2, 3, 4];
-        let mut ll: LinkedList<i32> = lst.into_iter().map(|x| x * x).collect();
-        assert_eq!(ll.pop(), Some(1));
-        assert_eq!(ll.pop(), Some(4));
-        assert_eq!(ll.pop(), Some(9));
-        assert_eq!(ll.pop(), Some(16));
+    /// The index of the first occurrence of `value` in the list
+    ///
+    /// Returns `None` if the value is not in the list.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 2]);
+    /// assert_eq!(ll.position(&2), Some(1));
+    /// assert_eq!(ll.position(&4), None);
+    /// ```
+    pub fn position(&self, value: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.iter().position(|data| data == value)
     }
 
-    #[test]
-    fn push_append_test() {
-        let mut ll = LinkedList::new();
-        ll.push(2);
-        ll.append(3);
+    /// Check if `needle` appears as a contiguous run in the list
+    ///
+    /// An empty needle is always contained.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3, 4]);
+    /// assert!(ll.contains_subsequence(&[2, 3]));
+    /// assert!(!ll.contains_subsequence(&[3, 2]));
+    /// ```
+    pub fn contains_subsequence(&self, needle: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        if needle.is_empty() {
+            return true;
+        }
+        let mut start = self;
+        while let Some((_, ref child)) = start.0 {
+            if start.iter().take(needle.len()).eq(needle.iter()) {
+                return true;
+            }
+            start = child;
+        }
+        false
+    }
+
+    /// Clone the list, but only if it has at most `max` items
+    ///
+    /// Counting stops as soon as the list turns out to be too long, in which
+    /// case the number of items seen (`max + 1`) is returned as the error,
+    /// without cloning anything.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3]);
+    /// assert!(ll.try_clone_up_to(3).is_ok());
+    /// assert_eq!(ll.try_clone_up_to(2).err(), Some(3));
+    /// ```
+    pub fn try_clone_up_to(&self, max: usize) -> Result<LinkedList<T>, usize>
+    where
+        T: Clone,
+    {
+        if self.iter().nth(max).is_some() {
+            Err(max + 1)
+        } else {
+            Ok(self.clone())
+        }
+    }
+
+    /// Get a reference to the value at index `n`
+    ///
+    /// Returns `None` if `n` is out of range.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2]);
+    /// assert_eq!(ll.get(1), Some(&2));
+    /// assert_eq!(ll.get(2), None);
+    /// ```
+    pub fn get(&self, n: usize) -> Option<&T> {
+        self.iter().nth(n)
+    }
+
+    /// Get a mutable reference to the value at index `n`
+    ///
+    /// Returns `None` if `n` is out of range.
+    ///
+    /// ```
+    /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2]);
+    /// if let Some(data) = ll.get_mut(1) {
+    ///     *data = 5;
+    /// }
+    /// assert_eq!(ll.get(1), Some(&5));
+    /// assert_eq!(ll.get_mut(2), None);
+    /// ```
+    pub fn get_mut(&mut self, n: usize) -> Option<&mut T> {
+        self.sublist_mut(n).0.as_mut().map(|(data, _)| data)
+    }
+
+    /// Get a reference to the value at `index`, or the length of the list
+    ///
+    /// When the index is out of range, the error holds the length of the
+    /// list.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2]);
+    /// assert_eq!(ll.get_checked(1), Ok(&2));
+    /// assert_eq!(ll.get_checked(5), Err(2));
+    /// ```
+    pub fn get_checked(&self, index: usize) -> Result<&T, usize> {
+        self.get(index).ok_or_else(|| self.len())
+    }
+
+    /// Check if the list starts with all items of `prefix`
+    ///
+    /// An empty prefix is the prefix of every list.
+    ///
+    /// ```
+    /// use data_structures::linkedlist::LinkedList;
+    ///
+    /// let ll = LinkedList::from_iter(vec![1, 2, 3]);
+    /// assert!(ll.starts_with(&LinkedList::from_iter(vec![1, 2])));
+    /// assert!(!ll.starts_with(&LinkedList::from_iter(vec![2, 3])));
+    /// ```
+    pub fn starts_with(&self, prefix: &LinkedList<T>) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut values = self.iter();
+        prefix.iter().all(|data| values.next() == Some(data))
+    }
+
+    /// Collapse runs of equal values into `(value, count)` pairs
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 1, 2, 3, 3, 3]);
+    /// assert_eq!(ll.run_length_encode(), vec![(1, 2), (2, 1), (3, 3)]);
+    /// ```
+    pub fn run_length_encode(&self) -> Vec<(T, usize)>
+    where
+        T: PartialEq + Clone,
+    {
+        let mut runs: Vec<(T, usize)> = Vec::new();
+        for data in self.iter() {
+            match runs.last_mut() {
+                Some((value, count)) if value == data => *count += 1,
+                _ => runs.push((data.clone(), 1)),
+            }
+        }
+        runs
+    }
+
+    /// Apply `f` to each value in the list, stopping at the first error
+    ///
+    /// The list is only borrowed, so it is unchanged afterwards.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3]);
+    /// let result = ll.try_for_each(|x| if *x < 2 { Ok(()) } else { Err(*x) });
+    /// assert_eq!(result, Err(2));
+    /// ```
+    pub fn try_for_each<E, F>(&self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&T) -> Result<(), E>,
+    {
+        for data in self.iter() {
+            f(data)?;
+        }
+        Ok(())
+    }
+
+    /// The arithmetic mean of the values in the list
+    ///
+    /// Returns None for an empty list.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3, 4]);
+    /// assert_eq!(ll.average(), Some(2.5));
+    /// ```
+    pub fn average(&self) -> Option<f64>
+    where
+        T: Into<f64> + Copy,
+    {
+        let (sum, count) = self.iter().fold((0.0, 0), |(sum, count), &data| {
+            (sum + data.into(), count + 1)
+        });
+        if count == 0 {
+            None
+        } else {
+            Some(sum / count as f64)
+        }
+    }
+
+    /// The sums of every sliding window of `window` items in the list
+    ///
+    /// The sum is updated incrementally as the window moves, by adding the
+    /// new item and subtracting the old one. The result is empty if the list
+    /// is shorter than the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `window` is zero.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3, 4]);
+    /// assert_eq!(ll.moving_sum(2), vec![3, 5, 7]);
+    /// ```
+    pub fn moving_sum(&self, window: usize) -> Vec<T>
+    where
+        T: std::iter::Sum + Copy + std::ops::Add<Output = T> + std::ops::Sub<Output = T>,
+    {
+        assert!(window > 0, "window size must be larger than zero");
+        let mut sums = Vec::new();
+        if self.len() < window {
+            return sums;
+        }
+
+        let mut ahead = self.iter().copied();
+        let mut sum: T = ahead.by_ref().take(window).sum();
+        sums.push(sum);
+        for (new, old) in ahead.zip(self.iter().copied()) {
+            sum = sum + new - old;
+            sums.push(sum);
+        }
+        sums
+    }
+
+    /// The maximum of every sliding window of `window` items in the list
+    ///
+    /// The candidates for the maximum are kept in a decreasing deque, so
+    /// every item is only handled once. The result is empty if the list is
+    /// shorter than the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics when `window` is zero.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 3, 2, 5, 4]);
+    /// assert_eq!(ll.moving_max(2), vec![3, 3, 5, 5]);
+    /// ```
+    pub fn moving_max(&self, window: usize) -> Vec<T>
+    where
+        T: Ord + Copy,
+    {
+        assert!(window > 0, "window size must be larger than zero");
+        let mut maxima = Vec::new();
+        let mut candidates: std::collections::VecDeque<(usize, T)> = Default::default();
+        for (i, &data) in self.iter().enumerate() {
+            // smaller candidates can never be the maximum while data is in the window
+            while candidates
+                .back()
+                .is_some_and(|&(_, candidate)| candidate <= data)
+            {
+                candidates.pop_back();
+            }
+            candidates.push_back((i, data));
+            if candidates[0].0 + window <= i {
+                candidates.pop_front();
+            }
+            if i + 1 >= window {
+                maxima.push(candidates[0].1);
+            }
+        }
+        maxima
+    }
+
+    /// Move the node at index `from` to index `to`
+    ///
+    /// The index `to` refers to the position after the node has been removed,
+    /// and is clamped to the end of the list. The nodes are relinked, so no
+    /// values are copied. Returns false (leaving the list unchanged) when
+    /// `from` is out of range.
+    ///
+    /// ```
+    /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3, 4]);
+    /// assert!(ll.move_node(0, 2));
+    /// let list: Vec<i32> = ll.into_iter().collect();
+    /// assert_eq!(list, vec![2, 3, 1, 4]);
+    /// ```
+    pub fn move_node(&mut self, from: usize, to: usize) -> bool {
+        let source = self.sublist_mut(from);
+        let Some((data, mut node)) = source.0.take() else {
+            return false;
+        };
+        source.0 = node.0.take();
+
+        let target = self.sublist_mut(to);
+        node.0 = target.0.take();
+        target.0 = Some((data, node));
+        true
+    }
+
+    /// Remove the first `n` items and return them as a new list
+    ///
+    /// The remainder stays in the list. When `n` is larger than the length of
+    /// the list, everything is moved into the returned list. The nodes are
+    /// moved, not copied.
+    ///
+    /// ```
+    /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3]);
+    /// let prefix: Vec<i32> = ll.take(2).into_iter().collect();
+    /// assert_eq!(prefix, vec![1, 2]);
+    /// assert_eq!(ll.pop(), Some(3));
+    /// assert_eq!(ll.pop(), None);
+    /// ```
+    pub fn take(&mut self, n: usize) -> LinkedList<T> {
+        let rest = self.sublist_mut(n).0.take();
+        LinkedList(std::mem::replace(&mut self.0, rest))
+    }
+
+    /// Keep the first `n` items in the list, and return the rest as a new list
+    ///
+    /// This is the counterpart of [`take`]. When `n` is larger than the
+    /// length of the list, the returned list is empty. The nodes are moved,
+    /// not copied.
+    ///
+    /// [`take`]: #method.take
+    ///
+    /// ```
+    /// use data_structures::linkedlist::LinkedList;
+    ///
+    /// let mut ll = LinkedList::from_iter(vec![1, 2, 3, 4]);
+    /// let rest = ll.split_off(1);
+    /// assert_eq!(ll, LinkedList::from_iter(vec![1]));
+    /// assert_eq!(rest, LinkedList::from_iter(vec![2, 3, 4]));
+    /// ```
+    pub fn split_off(&mut self, n: usize) -> LinkedList<T> {
+        LinkedList(self.sublist_mut(n).0.take())
+    }
+
+    /// Split the list at the first item matching `pred`, returning the rest
+    ///
+    /// With `inclusive`, the matching item starts the returned list;
+    /// otherwise it stays at the end of `self`. Returns `None`, leaving the
+    /// list untouched, if no item matches.
+    ///
+    /// ```
+    /// use data_structures::linkedlist::LinkedList;
+    ///
+    /// let mut ll = LinkedList::from_iter(vec![1, 2, 3, 4]);
+    /// let rest = ll.split_off_at(|&x| x == 3, true);
+    /// assert_eq!(rest, Some(LinkedList::from_iter(vec![3, 4])));
+    /// assert_eq!(ll, LinkedList::from_iter(vec![1, 2]));
+    ///
+    /// let mut ll = LinkedList::from_iter(vec![1, 2, 3, 4]);
+    /// let rest = ll.split_off_at(|&x| x == 3, false);
+    /// assert_eq!(rest, Some(LinkedList::from_iter(vec![4])));
+    /// assert_eq!(ll, LinkedList::from_iter(vec![1, 2, 3]));
+    /// ```
+    pub fn split_off_at<F>(&mut self, pred: F, inclusive: bool) -> Option<LinkedList<T>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let position = self.iter().position(pred)?;
+        let at = if inclusive { position } else { position + 1 };
+        Some(LinkedList(self.sublist_mut(at).0.take()))
+    }
+
+    /// Pop exactly `n` items from the front of the list
+    ///
+    /// When the list holds fewer than `n` items, all of them are popped and
+    /// returned as the error, leaving the list empty.
+    ///
+    /// ```
+    /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3]);
+    /// assert_eq!(ll.pop_n(2), Ok(vec![1, 2]));
+    /// assert_eq!(ll.pop_n(2), Err(vec![3]));
+    /// assert!(ll.is_empty());
+    /// ```
+    pub fn pop_n(&mut self, n: usize) -> Result<Vec<T>, Vec<T>> {
+        let popped: Vec<T> = self.take(n).into_iter().collect();
+        if popped.len() == n {
+            Ok(popped)
+        } else {
+            Err(popped)
+        }
+    }
+
+    // The part of the list starting at index n, or the empty end of the list
+    // when n is out of range
+    fn sublist_mut(&mut self, n: usize) -> &mut LinkedList<T> {
+        let mut cur = self;
+        for _ in 0..n {
+            match cur.0 {
+                Some((_, ref mut child)) => cur = child,
+                None => break,
+            }
+        }
+        cur
+    }
+
+    /// The number of items in the list
+    ///
+    /// The list does not store its length, so this walks the whole chain.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3]);
+    /// assert_eq!(ll.len(), 3);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Checks if there are no items in the list
+    ///
+    /// ```
+    /// let mut ll = data_structures::linkedlist::LinkedList::new();
+    /// assert!(ll.is_empty());
+    /// ll.push(1);
+    /// assert!(!ll.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Remove all items from the list
+    ///
+    /// The nodes are dropped one by one, like when the list itself is dropped.
+    ///
+    /// ```
+    /// let mut ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2]);
+    /// ll.clear();
+    /// assert!(ll.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+
+    /// The number of nodes in the chain, which is the length of the list
+    ///
+    /// Each node is nested in the previous one, so this is also the nesting
+    /// depth of the chain. The list operations walk the chain in a loop, so
+    /// their stack usage does not grow with this depth.
+    ///
+    /// ```
+    /// let ll = data_structures::linkedlist::LinkedList::from_iter(vec![1, 2, 3]);
+    /// assert_eq!(ll.depth(), 3);
+    /// ```
+    pub fn depth(&self) -> usize {
+        self.len()
+    }
+
+    // Remove all nodes at the head of the list equal to value
+    fn drop_leading(&mut self, value: &T)
+    where
+        T: PartialEq,
+    {
+        while matches!(self.0, Some((ref data, _)) if data == value) {
+            self.pop();
+        }
+    }
+
+    // The empty end of the list
+    fn tail_mut(&mut self) -> &mut LinkedList<T> {
+        let mut cur = self;
+        while let Some((_, ref mut child)) = cur.0 {
+            cur = child;
+        }
+        cur
+    }
+}
+
+// Stable sort of `items` which only compares with `<`, so unlike the sort of
+// the standard library it does not panic when the order is not total
+fn merge_sort<T: PartialOrd>(mut items: Vec<T>) -> Vec<T> {
+    if items.len() <= 1 {
+        return items;
+    }
+    let right = merge_sort(items.split_off(items.len() / 2));
+    let left = merge_sort(items);
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+        // equal items keep their order by taking from the left first
+        let next = if b < a { right.next() } else { left.next() };
+        merged.extend(next);
+    }
+    merged.extend(left);
+    merged.extend(right);
+    merged
+}
+
+impl<T> LinkedList<Option<T>> {
+    /// Unwrap all items, if none of them is `None`
+    ///
+    /// Returns `None` as soon as a `None` item is found, otherwise the list
+    /// of unwrapped items, in the same order.
+    ///
+    /// ```
+    /// use data_structures::linkedlist::LinkedList;
+    ///
+    /// let ll = LinkedList::from_iter(vec![Some(1), Some(2)]);
+    /// assert_eq!(ll.transpose_option(), Some(LinkedList::from_iter(vec![1, 2])));
+    ///
+    /// let ll = LinkedList::from_iter(vec![Some(1), None]);
+    /// assert_eq!(ll.transpose_option(), None);
+    /// ```
+    pub fn transpose_option(self) -> Option<LinkedList<T>> {
+        self.into_iter().collect()
+    }
+}
+
+impl<T, E> LinkedList<Result<T, E>> {
+    /// Unwrap all items, if none of them is an error
+    ///
+    /// Returns the first error that is found, otherwise the list of
+    /// unwrapped items, in the same order.
+    ///
+    /// ```
+    /// use data_structures::linkedlist::LinkedList;
+    ///
+    /// let ll: LinkedList<Result<i32, &str>> = LinkedList::from_iter(vec![Ok(1), Ok(2)]);
+    /// assert_eq!(ll.transpose_result(), Ok(LinkedList::from_iter(vec![1, 2])));
+    ///
+    /// let ll = LinkedList::from_iter(vec![Ok(1), Err("bad item")]);
+    /// assert_eq!(ll.transpose_result(), Err("bad item"));
+    /// ```
+    pub fn transpose_result(self) -> Result<LinkedList<T>, E> {
+        self.into_iter().collect()
+    }
+}
+
+/// Borrowing iterator over a [`LinkedList`], created by [`LinkedList::iter`]
+pub struct Iter<'a, T>(&'a LinkedList<T>);
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (data, child) = self.0 .0.as_ref()?;
+        self.0 = child;
+        Some(data)
+    }
+}
+
+/// Mutably borrowing iterator over a [`LinkedList`], created by
+/// [`LinkedList::iter_mut`]
+pub struct IterMut<'a, T>(Option<&'a mut LinkedList<T>>);
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (data, child) = self.0.take()?.0.as_mut()?;
+        self.0 = Some(child);
+        Some(data)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> Clone for LinkedList<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut values = self.iter();
+        LinkedList::from_fn(|| values.next().cloned())
+    }
+}
+
+impl<T> std::fmt::Debug for LinkedList<T>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for LinkedList<T>
+where
+    T: serde::Serialize,
+{
+    /// Serializes the items as a sequence, from head to tail
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for LinkedList<T>
+where
+    T: serde::Deserialize<'de>,
+{
+    /// Deserializes a sequence of items, from head to tail
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Vec::deserialize(deserializer).map(LinkedList::from)
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        LinkedList::new()
+    }
+}
+
+impl<T> PartialEq for LinkedList<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T> Eq for LinkedList<T> where T: Eq {}
+
+impl<T> std::hash::Hash for LinkedList<T>
+where
+    T: std::hash::Hash,
+{
+    /// Hashes the length, followed by the items from head to tail
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for data in self.iter() {
+            data.hash(state);
+        }
+    }
+}
+
+impl<T> Drop for LinkedList<T> {
+    // The default drop would recurse through every box, so unlink the nodes
+    // one by one instead
+    fn drop(&mut self) {
+        while let Some((_, mut child)) = self.0.take() {
+            self.0 = child.0.take();
+        }
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I>(list: I) -> Self
+    where
+        I: std::iter::IntoIterator<Item = T>,
+    {
+        let mut iter = list.into_iter();
+        LinkedList::from_fn(|| iter.next())
+    }
+}
+
+impl<T> From<Vec<T>> for LinkedList<T> {
+    fn from(vec: Vec<T>) -> Self {
+        LinkedList::from_iter(vec)
+    }
+}
+
+impl<T> From<LinkedList<T>> for Vec<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+/// Owning iterator over a [`LinkedList`], created by [`LinkedList::into_iter`]
+///
+/// The values are moved out of the list from the front.
+pub struct IntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop()
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter(self)
+    }
+}
+
+/// The Stack LIFO data structure.
+///
+/// LIFO is Last In First Out, and this realised by only using the pop and push methods on a Linked
+/// List
+/// ```
+/// let mut stack = data_structures::linkedlist::Stack::new();
+/// stack.push(1);
+/// stack.push(2);
+/// stack.push(3);
+/// assert_eq!(stack.pop(), Some(3));
+/// assert_eq!(stack.pop(), Some(2));
+/// assert_eq!(stack.pop(), Some(1));
+/// assert_eq!(stack.pop(), None);
+/// ```
+///
+/// The other methods of the linked list are not available on a stack:
+///
+/// ```compile_fail
+/// let mut stack = data_structures::linkedlist::Stack::new();
+/// stack.push(1);
+/// stack.append(2);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stack<T>(LinkedList<T>);
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Stack(LinkedList::new())
+    }
+
+    /// Put data on top of the stack
+    pub fn push(&mut self, data: T) {
+        self.0.push(data)
+    }
+
+    /// Remove the data on top of the stack
+    ///
+    /// When the stack is empty, None is returned.
+    pub fn pop(&mut self) -> Option<T> {
+        self.0.pop()
+    }
+
+    /// Get the data on top of the stack, without removing it
+    ///
+    /// ```
+    /// let mut stack = data_structures::linkedlist::Stack::new();
+    /// assert_eq!(stack.peek(), None);
+    /// stack.push(1);
+    /// stack.push(2);
+    /// assert_eq!(stack.peek(), Some(&2));
+    /// ```
+    pub fn peek(&self) -> Option<&T> {
+        self.0.peek()
+    }
+
+    /// The number of items on the stack
+    ///
+    /// ```
+    /// let mut stack = data_structures::linkedlist::Stack::new();
+    /// stack.push(1);
+    /// stack.push(2);
+    /// assert_eq!(stack.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Checks if there are no items on the stack
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Stack::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_ll_test() {
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert!(ll.0.is_none())
+    }
+
+    #[test]
+    fn append_ll_int_test() {
+        let mut ll = LinkedList(None);
+        ll.append(1);
+        assert!(ll.0.is_some());
+        let child = ll.0.take().unwrap();
+        assert_eq!(child.0, 1);
+        assert!(child.1 .0.is_none());
+    }
+
+    #[test]
+    fn append_ll_str_test() {
+        let mut ll = LinkedList(None);
+        ll.append("abc");
+        assert!(ll.0.is_some());
+        let child = ll.0.take().unwrap();
+        assert_eq!(child.0, "abc");
+        assert!(child.1 .0.is_none());
+    }
+
+    #[test]
+    fn pop_test() {
+        let mut ll = LinkedList::new();
+        ll.push(1);
+        ll.push(2);
+
+        assert_eq!(ll.pop(), Some(2));
+        assert_eq!(ll.pop(), Some(1));
+        assert_eq!(ll.pop(), None);
+        assert_eq!(ll.pop(), None);
+
+        ll.append(5);
+        assert_eq!(ll.pop(), Some(5));
+    }
+
+    #[test]
+    fn peek_test() {
+        let mut ll = LinkedList::new();
+        assert_eq!(ll.peek(), None);
+        ll.push(0);
+        assert_eq!(ll.peek(), Some(&0));
+    }
+
+    #[test]
+    fn last_test() {
+        let mut ll: LinkedList<i32> = LinkedList::new();
+        assert_eq!(ll.last(), None);
+        assert_eq!(ll.last_mut(), None);
+
+        // the head is also the tail
+        ll.push(1);
+        assert_eq!(ll.last(), Some(&1));
+        assert_eq!(ll.last(), ll.peek());
+        *ll.last_mut().unwrap() = 2;
+        assert_eq!(ll.peek(), Some(&2));
+
+        ll.append(3);
+        ll.append(4);
+        assert_eq!(ll.last(), Some(&4));
+        *ll.last_mut().unwrap() += 10;
+        assert_eq!(ll, LinkedList::from_iter(vec![2, 3, 14]));
+    }
+
+    #[test]
+    fn non_copy_test() {
+        let mut ll = LinkedList::new();
+        ll.push(String::from("b"));
+        ll.append(String::from("c"));
+        ll.insert(String::from("a"), 0);
+        assert_eq!(ll.peek(), Some(&String::from("a")));
+        assert_eq!(ll.pop(), Some(String::from("a")));
+
+        let rest: Vec<String> = ll.into_iter().collect();
+        assert_eq!(rest, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn insert_test() {
+        let mut ll = LinkedList::new();
+
+        ll.push(3);
+        ll.push(1);
+        ll.insert(2, 1);
+
+        assert_eq!(ll.pop(), Some(1));
+        assert_eq!(ll.pop(), Some(2));
+        assert_eq!(ll.pop(), Some(3));
+
+        let mut ll = LinkedList::new();
+        ll.insert(2, 0);
+        assert_eq!(ll.pop(), Some(2));
+    }
+
+    #[test]
+    fn splice_iter_test() {
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3]);
+        ll.splice_iter(1, vec![10, 11]);
+        assert_eq!(ll, LinkedList::from_iter(vec![1, 10, 11, 2, 3]));
+
+        ll.splice_iter(0, vec![-1]);
+        assert_eq!(ll, LinkedList::from_iter(vec![-1, 1, 10, 11, 2, 3]));
+
+        ll.splice_iter(99, vec![4, 5]);
+        assert_eq!(ll, LinkedList::from_iter(vec![-1, 1, 10, 11, 2, 3, 4, 5]));
+
+        ll.splice_iter(2, vec![]);
+        assert_eq!(ll.len(), 8);
+
+        let mut ll = LinkedList::new();
+        ll.splice_iter(3, vec![1, 2]);
+        assert_eq!(ll, LinkedList::from_iter(vec![1, 2]));
+    }
+
+    #[test]
+    fn retain_test() {
+        // removes the head, consecutive items and the last item
+        let mut ll = LinkedList::from_iter(vec![1, 3, 2, 5, 7, 4, 6, 9]);
+        ll.retain(|x| x % 2 == 0);
+        assert_eq!(ll, LinkedList::from_iter(vec![2, 4, 6]));
+
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3]);
+        ll.retain(|_| false);
+        assert!(ll.is_empty());
+
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3]);
+        ll.retain(|_| true);
+        assert_eq!(ll, LinkedList::from_iter(vec![1, 2, 3]));
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        empty.retain(|_| true);
+        assert!(empty.is_empty());
+
+        // items do not need to be Copy
+        let mut ll = LinkedList::from_iter(vec![String::from("ab"), String::from("c")]);
+        ll.retain(|s| s.len() > 1);
+        assert_eq!(ll, LinkedList::from_iter(vec![String::from("ab")]));
+    }
+
+    #[test]
+    fn remove_test() {
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3, 4, 5]);
+        assert_eq!(ll.remove(0), Some(1));
+        assert_eq!(ll, LinkedList::from_iter(vec![2, 3, 4, 5]));
+        assert_eq!(ll.remove(1), Some(3));
+        assert_eq!(ll, LinkedList::from_iter(vec![2, 4, 5]));
+        assert_eq!(ll.remove(2), Some(5));
+        assert_eq!(ll, LinkedList::from_iter(vec![2, 4]));
+        assert_eq!(ll.remove(2), None);
+        assert_eq!(ll.remove(10), None);
+        assert_eq!(ll, LinkedList::from_iter(vec![2, 4]));
+
+        let mut ll: LinkedList<i32> = LinkedList::new();
+        assert_eq!(ll.remove(0), None);
+    }
+
+    #[test]
+    fn insert_all_sorted_test() {
+        let mut ll = LinkedList::from_iter(vec![2, 4]);
+        ll.insert_all_sorted(vec![5, 1, 3]);
+        let list: Vec<i32> = ll.into_iter().collect();
+        assert_eq!(list, vec![1, 2, 3, 4, 5]);
+
+        let mut ll = LinkedList::new();
+        ll.insert_all_sorted(vec![2, 1]);
+        assert_eq!(ll.pop(), Some(1));
+        assert_eq!(ll.pop(), Some(2));
+        assert_eq!(ll.pop(), None);
+
+        // equal items keep the order of the batch, after the list items
+        let mut ll = LinkedList::from_iter(vec![(1, 'a')]);
+        ll.insert_all_sorted(vec![(2, 'x'), (1, 'b'), (0, 'y'), (1, 'c')]);
+        let list: Vec<(i32, char)> = ll.into_iter().collect();
+        assert_eq!(list, vec![(0, 'y'), (1, 'a'), (1, 'b'), (1, 'c'), (2, 'x')]);
+    }
+
+    #[test]
+    fn insert_all_sorted_nan_test() {
+        let mut ll = LinkedList::from_iter(vec![0.5, 2.5]);
+        // a batch which the sort of the standard library panics on
+        let batch: Vec<f64> = (0..50)
+            .map(|i| {
+                if i % 3 == 0 {
+                    f64::NAN
+                } else {
+                    (i * 37 % 101) as f64
+                }
+            })
+            .collect();
+        ll.insert_all_sorted(batch);
+        assert_eq!(ll.len(), 52);
+        assert_eq!(ll.iter().filter(|x| x.is_nan()).count(), 17);
+
+        let mut ll = LinkedList::new();
+        ll.insert_all_sorted(vec![3.0, 1.0, 2.0]);
+        let list: Vec<f64> = ll.into_iter().collect();
+        assert_eq!(list, vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn from_iter_rev_test() {
+        let mut ll = LinkedList::from_iter_rev(vec![1, 2, 3]);
+        assert_eq!(ll.pop(), Some(3));
+        assert_eq!(ll.pop(), Some(2));
+        assert_eq!(ll.pop(), Some(1));
+        assert_eq!(ll.pop(), None);
+
+        let ll: LinkedList<i32> = LinkedList::from_iter_rev(vec![]);
+        assert!(ll.0.is_none());
+    }
+
+    #[test]
+    fn into_reversed_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        let mut ll = ll.into_reversed();
+        assert_eq!(ll.pop(), Some(3));
+        assert_eq!(ll.pop(), Some(2));
+        assert_eq!(ll.pop(), Some(1));
+        assert_eq!(ll.pop(), None);
+
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert!(ll.into_reversed().0.is_none());
+    }
+
+    #[test]
+    fn iter_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        let first: Vec<&i32> = ll.iter().collect();
+        let second: Vec<&i32> = ll.iter().collect();
+        assert_eq!(first, vec![&1, &2, &3]);
+        assert_eq!(first, second);
+        assert_eq!(ll.peek(), Some(&1));
+        assert_eq!(ll.len(), 3);
+
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert_eq!(ll.iter().next(), None);
+    }
+
+    #[test]
+    fn iter_mut_test() {
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3]);
+        for data in &mut ll {
+            *data *= 2;
+        }
+        assert_eq!(ll, LinkedList::from_iter(vec![2, 4, 6]));
+
+        let mut ll: LinkedList<i32> = LinkedList::new();
+        assert_eq!(ll.iter_mut().next(), None);
+    }
+
+    #[test]
+    fn into_iter_ref_test() {
+        let ll = LinkedList::from_iter(vec![String::from("a"), String::from("b")]);
+        let mut joined = String::new();
+        for data in &ll {
+            joined.push_str(data);
+        }
+        assert_eq!(joined, "ab");
+        assert_eq!(ll.len(), 2);
+        assert_eq!(ll.peek(), Some(&String::from("a")));
+    }
+
+    #[test]
+    fn iter_indexed_test() {
+        let ll = LinkedList::from_iter(vec![10, 20, 30]);
+        let list: Vec<(usize, &i32)> = ll.iter_indexed().collect();
+        assert_eq!(list, vec![(0, &10), (1, &20), (2, &30)]);
+        assert_eq!(ll, LinkedList::from_iter(vec![10, 20, 30]));
+
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert_eq!(ll.iter_indexed().next(), None);
+    }
+
+    #[test]
+    fn from_fn_test() {
+        let mut count = 0;
+        let ll = LinkedList::from_fn(|| {
+            let current = count;
+            count += 1;
+            if current < 4 {
+                Some(current)
+            } else {
+                None
+            }
+        });
+        let list: Vec<i32> = ll.into_iter().collect();
+        assert_eq!(list, vec![0, 1, 2, 3]);
+
+        let ll: LinkedList<i32> = LinkedList::from_fn(|| None);
+        assert!(ll.0.is_none());
+    }
+
+    #[test]
+    fn reverse_test() {
+        let mut ll: LinkedList<i32> = LinkedList::new();
+        ll.reverse();
+        assert!(ll.is_empty());
+
+        let mut ll = LinkedList::from_iter(vec![1]);
+        ll.reverse();
+        assert_eq!(ll, LinkedList::from_iter(vec![1]));
+
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3, 4]);
+        ll.reverse();
+        assert_eq!(ll, LinkedList::from_iter(vec![4, 3, 2, 1]));
+
+        let mut ll = LinkedList::from_iter(vec![String::from("a"), String::from("b")]);
+        ll.reverse();
+        assert_eq!(ll.pop(), Some(String::from("b")));
+    }
+
+    #[test]
+    fn split_parity_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3, 4, 5]);
+        let (even, odd) = ll.split_parity();
+        assert_eq!(even.into_iter().collect::<Vec<i32>>(), vec![1, 3, 5]);
+        assert_eq!(odd.into_iter().collect::<Vec<i32>>(), vec![2, 4]);
+
+        let ll: LinkedList<i32> = LinkedList::new();
+        let (even, odd) = ll.split_parity();
+        assert!(even.0.is_none());
+        assert!(odd.0.is_none());
+    }
+
+    #[test]
+    fn reduce_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3, 4]);
+        assert_eq!(ll.reduce(|a, b| a + b), Some(10));
+
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        assert_eq!(ll.reduce(|a, b| a * 10 + b), Some(123));
+
+        let ll = LinkedList::from_iter(vec![7]);
+        assert_eq!(ll.reduce(|_, _| panic!("only one item")), Some(7));
+
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert_eq!(ll.reduce(|a, b| a + b), None);
+    }
+
+    #[test]
+    fn zip_longest_test() {
+        let a = LinkedList::from_iter(vec![1, 2, 3]);
+        let b = LinkedList::from_iter(vec![9]);
+        let pairs: Vec<(i32, i32)> = a.zip_longest(b, 0).into_iter().collect();
+        assert_eq!(pairs, vec![(1, 9), (2, 0), (3, 0)]);
+
+        let a = LinkedList::from_iter(vec![1]);
+        let b = LinkedList::from_iter(vec![7, 8]);
+        let pairs: Vec<(i32, i32)> = a.zip_longest(b, -1).into_iter().collect();
+        assert_eq!(pairs, vec![(1, 7), (-1, 8)]);
+
+        let a: LinkedList<i32> = LinkedList::new();
+        assert!(a.zip_longest(LinkedList::new(), 0).0.is_none());
+    }
+
+    #[test]
+    fn split_when_test() {
+        let ll: LinkedList<i32> = LinkedList::from_iter(vec![1, 2, 5, 6, 1]);
+        let lists: Vec<Vec<i32>> = ll
+            .split_when(|prev, curr| (curr - prev).abs() > 2)
+            .into_iter()
+            .map(|list| list.into_iter().collect())
+            .collect();
+        assert_eq!(lists, vec![vec![1, 2], vec![5, 6], vec![1]]);
+
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        assert_eq!(ll.split_when(|_, _| false).len(), 1);
+
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert!(ll.split_when(|_, _| true).is_empty());
+    }
+
+    #[test]
+    fn union_sorted_test() {
+        let a = LinkedList::from_iter(vec![1, 2, 4]);
+        let b = LinkedList::from_iter(vec![2, 3, 4]);
+        let list: Vec<i32> = a.union_sorted(b).into_iter().collect();
+        assert_eq!(list, vec![1, 2, 3, 4]);
+
+        let a = LinkedList::from_iter(vec![1, 1, 5]);
+        let b = LinkedList::from_iter(vec![0, 1, 6, 6]);
+        let list: Vec<i32> = a.union_sorted(b).into_iter().collect();
+        assert_eq!(list, vec![0, 1, 5, 6]);
+
+        let a = LinkedList::from_iter(vec![1, 2]);
+        let list: Vec<i32> = a.union_sorted(LinkedList::new()).into_iter().collect();
+        assert_eq!(list, vec![1, 2]);
+    }
+
+    #[test]
+    fn flat_map_test() {
+        let ll = LinkedList::from_iter(vec![1, 2]);
+        let list: Vec<i32> = ll.flat_map(|x| vec![x, x * 10]).into_iter().collect();
+        assert_eq!(list, vec![1, 10, 2, 20]);
+
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        let list: Vec<i32> = ll.flat_map(|x| 0..x).into_iter().collect();
+        assert_eq!(list, vec![0, 0, 1, 0, 1, 2]);
+
+        let ll = LinkedList::from_iter(vec![1, 2]);
+        let ll: LinkedList<i32> = ll.flat_map(|_| None);
+        assert!(ll.0.is_none());
+    }
+
+    #[test]
+    fn coalesce_test() {
+        let sum_runs = |a: &(i32, i32), b: &(i32, i32)| {
+            if a.0 == b.0 {
+                Some((a.0, a.1 + b.1))
+            } else {
+                None
+            }
+        };
+        let ll = LinkedList::from_iter(vec![(1, 1), (1, 1), (2, 2), (3, 3), (3, 3)]);
+        let list: Vec<(i32, i32)> = ll.coalesce(sum_runs).into_iter().collect();
+        assert_eq!(list, vec![(1, 2), (2, 2), (3, 6)]);
+
+        // merged values take part in the next comparison
+        let sum_equal = |a: &i32, b: &i32| if a == b { Some(a + b) } else { None };
+        let ll = LinkedList::from_iter(vec![1, 1, 2, 3, 3]);
+        let list: Vec<i32> = ll.coalesce(sum_equal).into_iter().collect();
+        assert_eq!(list, vec![4, 6]);
+
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        let list: Vec<i32> = ll.coalesce(|_, _| None).into_iter().collect();
+        assert_eq!(list, vec![1, 2, 3]);
+
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert!(ll.coalesce(sum_equal).is_empty());
+    }
+
+    #[test]
+    fn contains_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        assert!(ll.contains(&1));
+        assert!(ll.contains(&2));
+        assert!(ll.contains(&3));
+        assert!(!ll.contains(&4));
+
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert!(!ll.contains(&1));
+    }
+
+    #[test]
+    fn position_test() {
+        let ll = LinkedList::from_iter(vec![String::from("a"), "b".into(), "c".into(), "b".into()]);
+        assert_eq!(ll.position(&"a".to_string()), Some(0));
+        assert_eq!(ll.position(&"b".to_string()), Some(1));
+        assert_eq!(ll.position(&"c".to_string()), Some(2));
+        assert_eq!(ll.position(&"d".to_string()), None);
+
+        let ll: LinkedList<String> = LinkedList::new();
+        assert_eq!(ll.position(&"a".to_string()), None);
+    }
+
+    #[test]
+    fn contains_subsequence_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3, 4]);
+        assert!(ll.contains_subsequence(&[2, 3]));
+        assert!(ll.contains_subsequence(&[3, 4]));
+        assert!(!ll.contains_subsequence(&[2, 4]));
+        assert!(!ll.contains_subsequence(&[4, 5]));
+        assert!(ll.contains_subsequence(&[]));
+
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert!(ll.contains_subsequence(&[]));
+        assert!(!ll.contains_subsequence(&[1]));
+    }
+
+    #[test]
+    fn try_clone_up_to_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        let list: Vec<i32> = ll.try_clone_up_to(3).unwrap().into_iter().collect();
+        assert_eq!(list, vec![1, 2, 3]);
+        assert!(ll.try_clone_up_to(10).is_ok());
+        assert_eq!(ll.try_clone_up_to(2).err(), Some(3));
+        assert_eq!(ll.try_clone_up_to(0).err(), Some(1));
+
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert!(ll.try_clone_up_to(0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn get_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        assert_eq!(ll.get(0), Some(&1));
+        assert_eq!(ll.get(1), Some(&2));
+        assert_eq!(ll.get(2), Some(&3));
+        assert_eq!(ll.get(3), None);
+
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert_eq!(ll.get(0), None);
+    }
+
+    #[test]
+    fn get_mut_test() {
+        let mut ll = LinkedList::from_iter(vec![String::from("a"), String::from("b")]);
+        ll.get_mut(0).unwrap().push('x');
+        ll.get_mut(1).unwrap().push('y');
+        assert_eq!(ll.get_mut(2), None);
+        assert_eq!(
+            ll,
+            LinkedList::from_iter(vec!["ax".to_string(), "by".to_string()])
+        );
+
+        let mut ll: LinkedList<i32> = LinkedList::new();
+        assert_eq!(ll.get_mut(0), None);
+    }
+
+    #[test]
+    fn get_checked_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        assert_eq!(ll.get_checked(0), Ok(&1));
+        assert_eq!(ll.get_checked(2), Ok(&3));
+        assert_eq!(ll.get_checked(3), Err(3));
+        assert_eq!(ll.get_checked(10), Err(3));
+
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert_eq!(ll.get_checked(0), Err(0));
+    }
+
+    #[test]
+    fn starts_with_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        assert!(ll.starts_with(&LinkedList::from_iter(vec![1, 2])));
+        assert!(ll.starts_with(&LinkedList::from_iter(vec![1, 2, 3])));
+        assert!(!ll.starts_with(&LinkedList::from_iter(vec![1, 3])));
+        assert!(!ll.starts_with(&LinkedList::from_iter(vec![1, 2, 3, 4])));
+        assert!(ll.starts_with(&LinkedList::new()));
+
+        let empty: LinkedList<i32> = LinkedList::new();
+        assert!(empty.starts_with(&LinkedList::new()));
+        assert!(!empty.starts_with(&LinkedList::from_iter(vec![1])));
+    }
+
+    #[test]
+    fn run_length_encode_test() {
+        let ll = LinkedList::from_iter(vec![1, 1, 2, 3, 3, 3]);
+        assert_eq!(ll.run_length_encode(), vec![(1, 2), (2, 1), (3, 3)]);
+
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert_eq!(ll.run_length_encode(), vec![]);
+    }
+
+    #[test]
+    fn try_for_each_test() {
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3, 4]);
+        let mut seen = Vec::new();
+        let result = ll.try_for_each(|x| {
+            seen.push(*x);
+            if *x == 3 {
+                Err(format!("bad value {}", x))
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(result, Err(String::from("bad value 3")));
+        assert_eq!(seen, vec![1, 2, 3]);
+
+        let mut sum = 0;
+        let result: Result<(), ()> = ll.try_for_each(|x| {
+            sum += x;
+            Ok(())
+        });
+        assert_eq!(result, Ok(()));
+        assert_eq!(sum, 10);
+        assert_eq!(ll.pop(), Some(1));
+    }
+
+    #[test]
+    fn average_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3, 4]);
+        assert_eq!(ll.average(), Some(2.5));
+        let ll = LinkedList::from_iter(vec![-1.5f32]);
+        assert_eq!(ll.average(), Some(-1.5));
+        let ll: LinkedList<u8> = LinkedList::new();
+        assert_eq!(ll.average(), None);
+    }
+
+    #[test]
+    fn moving_sum_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3, 4]);
+        assert_eq!(ll.moving_sum(2), vec![3, 5, 7]);
+        assert_eq!(ll.moving_sum(1), vec![1, 2, 3, 4]);
+        assert_eq!(ll.moving_sum(4), vec![10]);
+        assert!(ll.moving_sum(5).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn moving_sum_zero_window_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3, 4]);
+        ll.moving_sum(0);
+    }
+
+    #[test]
+    fn moving_max_test() {
+        let ll = LinkedList::from_iter(vec![1, 3, 2, 5, 4]);
+        assert_eq!(ll.moving_max(2), vec![3, 3, 5, 5]);
+        assert_eq!(ll.moving_max(3), vec![3, 5, 5]);
+        assert_eq!(ll.moving_max(1), vec![1, 3, 2, 5, 4]);
+        assert_eq!(ll.moving_max(5), vec![5]);
+        assert!(ll.moving_max(6).is_empty());
+
+        let ll = LinkedList::from_iter(vec![5, 4, 3, 2, 1]);
+        assert_eq!(ll.moving_max(2), vec![5, 4, 3, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn moving_max_zero_window_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3, 4]);
+        ll.moving_max(0);
+    }
+
+    #[test]
+    fn move_node_test() {
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3, 4]);
+        assert!(ll.move_node(0, 2));
+        let list: Vec<i32> = ll.into_iter().collect();
+        assert_eq!(list, vec![2, 3, 1, 4]);
+
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3, 4]);
+        assert!(ll.move_node(3, 0));
+        assert!(ll.move_node(1, 99));
+        let list: Vec<i32> = ll.into_iter().collect();
+        assert_eq!(list, vec![4, 2, 3, 1]);
+
+        let mut ll = LinkedList::from_iter(vec![1, 2]);
+        assert!(!ll.move_node(2, 0));
+        let list: Vec<i32> = ll.into_iter().collect();
+        assert_eq!(list, vec![1, 2]);
+    }
+
+    #[test]
+    fn take_test() {
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3]);
+        assert!(ll.take(0).0.is_none());
+        assert_eq!(ll.depth(), 3);
+
+        let prefix: Vec<i32> = ll.take(1).into_iter().collect();
+        assert_eq!(prefix, vec![1]);
+        let rest: Vec<i32> = ll.clone().into_iter().collect();
+        assert_eq!(rest, vec![2, 3]);
+
+        let prefix: Vec<i32> = ll.take(2).into_iter().collect();
+        assert_eq!(prefix, vec![2, 3]);
+        assert!(ll.0.is_none());
+
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3]);
+        let prefix: Vec<i32> = ll.take(10).into_iter().collect();
+        assert_eq!(prefix, vec![1, 2, 3]);
+        assert!(ll.0.is_none());
+    }
+
+    #[test]
+    fn split_off_test() {
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3, 4, 5]);
+        let rest = ll.split_off(2);
+        assert_eq!(ll, LinkedList::from_iter(vec![1, 2]));
+        assert_eq!(rest, LinkedList::from_iter(vec![3, 4, 5]));
+
+        // everything moves into the returned list
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3]);
+        let rest = ll.split_off(0);
+        assert!(ll.is_empty());
+        assert_eq!(rest, LinkedList::from_iter(vec![1, 2, 3]));
+
+        // at and past the end nothing moves
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3]);
+        assert!(ll.split_off(3).is_empty());
+        assert!(ll.split_off(10).is_empty());
+        assert_eq!(ll, LinkedList::from_iter(vec![1, 2, 3]));
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        assert!(empty.split_off(0).is_empty());
+
+        // items do not need to be Copy
+        let mut ll = LinkedList::from_iter(vec![String::from("a"), String::from("b")]);
+        let rest = ll.split_off(1);
+        assert_eq!(ll.peek().map(String::as_str), Some("a"));
+        assert_eq!(rest.peek().map(String::as_str), Some("b"));
+        assert_eq!(ll.len(), 1);
+        assert_eq!(rest.len(), 1);
+    }
+
+    #[test]
+    fn split_off_at_test() {
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3, 2]);
+        let rest = ll.split_off_at(|&x| x == 2, true).unwrap();
+        assert_eq!(rest, LinkedList::from_iter(vec![2, 3, 2]));
+        assert_eq!(ll, LinkedList::from_iter(vec![1]));
+
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3, 2]);
+        let rest = ll.split_off_at(|&x| x == 2, false).unwrap();
+        assert_eq!(rest, LinkedList::from_iter(vec![3, 2]));
+        assert_eq!(ll, LinkedList::from_iter(vec![1, 2]));
+
+        // matches at either end
+        let mut ll = LinkedList::from_iter(vec![1, 2]);
+        assert_eq!(
+            ll.split_off_at(|&x| x == 1, true),
+            Some(LinkedList::from_iter(vec![1, 2]))
+        );
+        assert!(ll.is_empty());
+        let mut ll = LinkedList::from_iter(vec![1, 2]);
+        assert_eq!(ll.split_off_at(|&x| x == 2, false), Some(LinkedList::new()));
+        assert_eq!(ll, LinkedList::from_iter(vec![1, 2]));
+
+        let mut ll = LinkedList::from_iter(vec![1, 2]);
+        assert_eq!(ll.split_off_at(|&x| x == 5, true), None);
+        assert_eq!(ll, LinkedList::from_iter(vec![1, 2]));
+    }
+
+    #[test]
+    fn pop_n_test() {
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3, 4, 5]);
+        assert_eq!(ll.pop_n(0), Ok(vec![]));
+        assert_eq!(ll.pop_n(3), Ok(vec![1, 2, 3]));
+        assert_eq!(ll.len(), 2);
+
+        assert_eq!(ll.pop_n(3), Err(vec![4, 5]));
+        assert!(ll.is_empty());
+        assert_eq!(ll.pop_n(1), Err(vec![]));
+    }
+
+    #[test]
+    fn len_test() {
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert_eq!(ll.len(), 0);
+        assert!(ll.is_empty());
+
+        let mut ll = LinkedList::new();
+        ll.push(1);
+        assert_eq!(ll.len(), 1);
+        assert!(!ll.is_empty());
+
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        assert_eq!(ll.len(), 3);
+        assert!(!ll.is_empty());
+        // the list is only borrowed
+        assert_eq!(ll.peek(), Some(&1));
+    }
+
+    #[test]
+    fn transpose_option_test() {
+        let ll = LinkedList::from_iter(vec![Some(1), Some(2), Some(3)]);
+        assert_eq!(
+            ll.transpose_option(),
+            Some(LinkedList::from_iter(vec![1, 2, 3]))
+        );
+
+        let ll = LinkedList::from_iter(vec![Some(1), None, Some(3)]);
+        assert_eq!(ll.transpose_option(), None);
+
+        let ll: LinkedList<Option<i32>> = LinkedList::new();
+        assert_eq!(ll.transpose_option(), Some(LinkedList::new()));
+    }
+
+    #[test]
+    fn transpose_result_test() {
+        let ll: LinkedList<Result<i32, i32>> = LinkedList::from_iter(vec![Ok(1), Ok(2)]);
+        assert_eq!(ll.transpose_result(), Ok(LinkedList::from_iter(vec![1, 2])));
+
+        // the first error is returned
+        let ll = LinkedList::from_iter(vec![Ok(1), Err(2), Ok(3), Err(4)]);
+        assert_eq!(ll.transpose_result(), Err(2));
+    }
+
+    #[test]
+    fn default_test() {
+        let ll: LinkedList<String> = Default::default();
+        assert!(ll.is_empty());
+
+        let stack: Stack<i32> = Stack::default();
+        assert_eq!(stack.peek(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        let json = serde_json::to_string(&ll).unwrap();
+        assert_eq!(json, "[1,2,3]");
+        let back: LinkedList<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, ll);
+
+        let empty: LinkedList<String> = LinkedList::new();
+        let json = serde_json::to_string(&empty).unwrap();
+        assert_eq!(json, "[]");
+        let back: LinkedList<String> = serde_json::from_str(&json).unwrap();
+        assert!(back.is_empty());
+    }
+
+    #[test]
+    fn stack_test() {
+        let mut stack = Stack::new();
+        assert!(stack.is_empty());
+        assert_eq!(stack.len(), 0);
+        stack.push(1);
+        stack.push(2);
+        assert_eq!(stack.peek(), Some(&2));
+        stack.push(3);
+        assert_eq!(stack.len(), 3);
+        assert!(!stack.is_empty());
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), Some(2));
+        stack.push(4);
+        assert_eq!(stack.pop(), Some(4));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+        assert_eq!(stack.peek(), None);
+        assert!(stack.is_empty());
+
+        // items do not need to be Copy
+        let mut stack = Stack::new();
+        stack.push(String::from("a"));
+        stack.push(String::from("b"));
+        assert_eq!(stack.pop().as_deref(), Some("b"));
+        assert_eq!(stack.peek().map(String::as_str), Some("a"));
+    }
+
+    #[test]
+    fn clear_test() {
+        let mut ll = LinkedList::from_iter(vec![1, 2, 3]);
+        ll.clear();
+        assert_eq!(ll.peek(), None);
+        assert_eq!(ll.pop(), None);
+        assert_eq!(ll, LinkedList::new());
+
+        ll.push(4);
+        assert_eq!(ll.pop(), Some(4));
+    }
+
+    #[test]
+    fn depth_test() {
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert_eq!(ll.depth(), 0);
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        assert_eq!(ll.depth(), 3);
+    }
+
+    #[test]
+    fn clone_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        let list: Vec<i32> = ll.clone().into_iter().collect();
+        assert_eq!(list, vec![1, 2, 3]);
+        assert_eq!(ll.peek(), Some(&1));
+    }
+
+    #[test]
+    fn debug_test() {
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        assert_eq!(format!("{:?}", ll), "[1, 2, 3]");
+        assert_eq!(ll.peek(), Some(&1));
+
+        let ll: LinkedList<i32> = LinkedList::new();
+        assert_eq!(format!("{:?}", ll), "[]");
+
+        let ll = LinkedList::from_iter(vec!["a", "b"]);
+        assert_eq!(format!("{:?}", ll), r#"["a", "b"]"#);
+    }
+
+    #[test]
+    fn hash_test() {
+        use std::collections::HashSet;
+        use std::hash::{DefaultHasher, Hash, Hasher};
+
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut set = HashSet::new();
+        set.insert(LinkedList::from_iter(vec![
+            String::from("a"),
+            String::from("b"),
+        ]));
+        set.insert(LinkedList::from_iter(vec![
+            String::from("a"),
+            String::from("b"),
+        ]));
+        assert_eq!(set.len(), 1);
+        assert!(set.contains(&LinkedList::from_iter(vec![
+            String::from("a"),
+            String::from("b")
+        ])));
+
+        let ll = LinkedList::from_iter(vec![1, 2, 3]);
+        assert_eq!(hash_of(&ll), hash_of(&ll.clone()));
+        assert_ne!(hash_of(&ll), hash_of(&LinkedList::from_iter(vec![3, 2, 1])));
+        assert_ne!(hash_of(&ll), hash_of(&LinkedList::from_iter(vec![1, 2])));
+        assert_ne!(hash_of(&ll), hash_of(&LinkedList::<i32>::new()));
+    }
+
+    #[test]
+    fn eq_test() {
+        assert_eq!(LinkedList::<i32>::new(), LinkedList::new());
+        assert_eq!(
+            LinkedList::from_iter(vec![1, 2, 3]),
+            LinkedList::from_iter(vec![1, 2, 3])
+        );
+
+        // a prefix is not equal to the full list, in either direction
+        let prefix = LinkedList::from_iter(vec![1, 2]);
+        let full = LinkedList::from_iter(vec![1, 2, 3]);
+        assert_ne!(prefix, full);
+        assert_ne!(full, prefix);
+        assert_ne!(LinkedList::new(), full);
+
+        assert_ne!(
+            LinkedList::from_iter(vec![1, 2, 3]),
+            LinkedList::from_iter(vec![3, 2, 1])
+        );
+
+        // no Copy or Clone needed
+        let strings = LinkedList::from_iter(vec![String::from("a")]);
+        assert_eq!(strings, LinkedList::from_iter(vec![String::from("a")]));
+    }
+
+    #[test]
+    fn deep_list_test() {
+        // none of the operations should recurse, so a small stack is enough
+        let handle = std::thread::Builder::new()
+            .stack_size(64 * 1024)
+            .spawn(|| {
+                let mut ll = LinkedList::from_iter((0..100_000).rev());
+                ll.append(-1);
+                ll.insert(100_000, 50_000);
+
+                let mut sorted = LinkedList::new();
+                sorted.insert_all_sorted(ll);
+                assert_eq!(sorted.depth(), 100_002);
+
+                let mut copy = sorted.clone();
+                for i in -1..=100_000 {
+                    assert_eq!(sorted.pop(), Some(i));
+                    assert_eq!(copy.pop(), Some(i));
+                }
+                assert_eq!(sorted.pop(), None);
+            })
+            .unwrap();
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn append_large_test() {
+        // appending walks to the tail in a loop, so a long list is fine;
+        // the bulk is built with from_iter, as repeated appends are quadratic
+        let mut ll = LinkedList::from_iter(0..999_999);
+        ll.append(999_999);
+        assert_eq!(ll.pop(), Some(0));
+        assert_eq!(ll.take(999_998).depth(), 999_998);
+        assert_eq!(ll.pop(), Some(999_999));
+    }
+
+    #[test]
+    fn drop_large_test() {
+        fn build_and_drop() {
+            let ll = LinkedList::from_iter(0..2_000_000);
+            assert_eq!(ll.depth(), 2_000_000);
+        }
+        build_and_drop();
+    }
+
+    #[test]
+    fn from_test() {
+        let lst = vec![1, 2, 3, 4];
+        let mut ll = LinkedList::from_iter(lst);
+        assert_eq!(ll.pop(), Some(1));
+        assert_eq!(ll.pop(), Some(2));
+        assert_eq!(ll.pop(), Some(3));
+        assert_eq!(ll.pop(), Some(4));
+    }
+
+    #[test]
+    fn from_empty_test() {
+        let lst: Vec<i32> = Vec::new();
+        let mut ll = LinkedList::from_iter(lst);
+        assert_eq!(ll.pop(), None);
+    }
+
+    #[test]
+    fn from_vec_test() {
+        let ll = LinkedList::from(vec![String::from("a"), String::from("b")]);
+        assert_eq!(ll.peek(), Some(&String::from("a")));
+        let vec: Vec<String> = ll.into();
+        assert_eq!(vec, vec!["a", "b"]);
+
+        let ll = LinkedList::from(Vec::<i32>::new());
+        assert!(ll.is_empty());
+        assert_eq!(Vec::from(ll), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn from_map_test() {
+        let lst = vec![1, 2, 3, 4];
+        let mut ll: LinkedList<i32> = lst.into_iter().map(|x| x * x).collect();
+        assert_eq!(ll.pop(), Some(1));
+        assert_eq!(ll.pop(), Some(4));
+        assert_eq!(ll.pop(), Some(9));
+        assert_eq!(ll.pop(), Some(16));
+    }
+
+    #[test]
+    fn push_append_test() {
+        let mut ll = LinkedList::new();
+        ll.push(2);
+        ll.append(3);
         ll.push(1);
         ll.append(4);
         assert_eq!(ll.pop(), Some(1));
@@ -277,28 +2379,12 @@ mod tests {
     #[test]
     fn iterator_test() {
         let ll = LinkedList::from_iter(vec![0, 1, 2, 3, 4]);
-        for (a, b) in ll.enumerate() {
+        for (a, b) in ll.into_iter().enumerate() {
             assert_eq!(a, b)
         }
 
         let ll = LinkedList::from_iter(vec![0, 1, 2, 3, 4]);
-        let vec: Vec<i32> = ll.collect();
+        let vec: Vec<i32> = ll.into_iter().collect();
         assert_eq!(vec, vec![0, 1, 2, 3, 4]);
     }
 }
-
-/// The Stack LIFO data structure.
-///
-/// LIFO is Last In First Out, and this realised by only using the pop and push methods on a Linked
-/// List
-/// ```
-/// let mut stack = data_structures::linkedlist::Stack::new();
-/// stack.push(1);
-/// stack.push(2);
-/// stack.push(3);
-/// assert_eq!(stack.pop(), Some(3));
-/// assert_eq!(stack.pop(), Some(2));
-/// assert_eq!(stack.pop(), Some(1));
-/// assert_eq!(stack.pop(), None);
-/// ```
-pub type Stack<T> = LinkedList<T>;
@@ -1,9 +1,40 @@
 pub mod priority_queue {
-    use crate::linkedlist::LinkedList;
+    use std::cmp::Ordering;
 
-    /// Priority queue, with increasing order based on a linked list
-    pub struct PriorityQueue<T> {
-        list: LinkedList<T>,
+    /// Priority queue, with increasing order based on a binary heap
+    ///
+    /// Inserting and popping an item both take `O(log n)` time. Items which
+    /// are equal in the order are popped in the order they were inserted.
+    ///
+    /// The order is given by a comparator function `C`, which by default
+    /// compares the items with [`PartialOrd`]. Use [`with_comparator`] for any
+    /// other order.
+    ///
+    /// [`with_comparator`]: #method.with_comparator
+    ///
+    /// To get a queue which pops the largest item first, wrap the items in
+    /// [`std::cmp::Reverse`]:
+    ///
+    /// ```
+    /// use std::cmp::Reverse;
+    ///
+    /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+    /// queue.insert(Reverse(1));
+    /// queue.insert(Reverse(3));
+    /// queue.insert(Reverse(2));
+    /// assert_eq!(queue.pop(), Some(Reverse(3)));
+    /// ```
+    #[derive(Clone)]
+    pub struct PriorityQueue<T, C = fn(&T, &T) -> Ordering> {
+        // binary heap, where every item is ordered before its children by
+        // compare, so the first item is the next to be popped
+        heap: Vec<T>,
+        // sequence number of the item at the same position in heap, which
+        // breaks ties between equal items
+        seqs: Vec<usize>,
+        compare: C,
+        // sequence number of the next inserted item
+        seq: usize,
     }
 
     impl<T> PriorityQueue<T>
@@ -11,13 +42,71 @@ pub mod priority_queue {
         T: Copy + PartialOrd,
     {
         pub fn new() -> Self {
+            PriorityQueue::with_comparator(partial_compare)
+        }
+
+        /// Create a priority queue which pops the largest item first
+        ///
+        /// This is the reverse of the default order of [`new`], without
+        /// wrapping the items in [`std::cmp::Reverse`].
+        ///
+        /// [`new`]: #method.new
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new_max();
+        /// queue.insert(1);
+        /// queue.insert(3);
+        /// queue.insert(2);
+        /// assert_eq!(queue.pop(), Some(3));
+        /// ```
+        pub fn new_max() -> Self {
+            PriorityQueue::with_comparator(reverse_partial_compare)
+        }
+    }
+
+    impl<T> Default for PriorityQueue<T>
+    where
+        T: Copy + PartialOrd,
+    {
+        fn default() -> Self {
+            PriorityQueue::new()
+        }
+    }
+
+    impl<T, C> PriorityQueue<T, C>
+    where
+        T: Copy,
+        C: Fn(&T, &T) -> Ordering,
+    {
+        /// Create a priority queue which is ordered by `compare`
+        ///
+        /// Items for which `compare` returns `Ordering::Less` are popped first.
+        /// The items do not need to implement [`PartialOrd`].
+        ///
+        /// ```
+        /// let mut queue =
+        ///     data_structures::queues::priority_queue::PriorityQueue::with_comparator(
+        ///         |a: &i32, b: &i32| b.cmp(a),
+        ///     );
+        /// queue.insert(1);
+        /// queue.insert(3);
+        /// queue.insert(2);
+        /// assert_eq!(queue.pop(), Some(3));
+        /// ```
+        pub fn with_comparator(compare: C) -> Self {
             Self {
-                list: LinkedList::new(),
+                heap: Vec::new(),
+                seqs: Vec::new(),
+                compare,
+                seq: 0,
             }
         }
 
         /// Add data (in increasing order) to the priority queue.
         ///
+        /// Data which is equal to items already in the queue is popped after
+        /// those items.
+        ///
         /// ```
         /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
         /// queue.insert(1);
@@ -27,7 +116,61 @@ pub mod priority_queue {
         /// assert_eq!(list, vec![1, 2, 3]);
         /// ```
         pub fn insert(&mut self, data: T) {
-            insert_inorder(&mut self.list, data)
+            self.heap.push(data);
+            self.seqs.push(self.seq);
+            self.seq += 1;
+            self.sift_up(self.heap.len() - 1);
+        }
+
+        /// Replace the first item matching `find` by `value`, or insert it
+        ///
+        /// Either way, `value` ends up at its place in the order of the queue.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+        /// queue.insert(1);
+        /// queue.insert(2);
+        /// queue.upsert(|x| *x == 1, 3);
+        /// assert_eq!(queue.pop(), Some(2));
+        /// assert_eq!(queue.pop(), Some(3));
+        /// ```
+        pub fn upsert<F>(&mut self, find: F, value: T)
+        where
+            F: FnMut(&T) -> bool,
+        {
+            if let Some(i) = self.heap.iter().position(find) {
+                self.remove_at(i);
+            }
+            self.insert(value);
+        }
+
+        /// Move all items of `other` into the queue
+        ///
+        /// The items are ordered by the comparator of this queue. Both heaps
+        /// are combined and the order is restored at once, in linear time.
+        /// Items of `other` are popped after equal items already in the
+        /// queue.
+        ///
+        /// ```
+        /// use data_structures::queues::priority_queue::PriorityQueue;
+        ///
+        /// let mut queue = PriorityQueue::new();
+        /// queue.insert(1);
+        /// queue.insert(4);
+        /// let mut other = PriorityQueue::new();
+        /// other.insert(3);
+        /// other.insert(2);
+        /// queue.merge(other);
+        /// assert_eq!(queue.into_sorted_vec(), vec![1, 2, 3, 4]);
+        /// ```
+        pub fn merge(&mut self, other: Self) {
+            // keep the insertion order of other, after all items of self
+            let seq = self.seq;
+            self.heap.extend(other.heap);
+            self.seqs
+                .extend(other.seqs.into_iter().map(|other_seq| seq + other_seq));
+            self.seq += other.seq;
+            self.rebuild();
         }
 
         /// Remove data in increasing order from the queue
@@ -46,290 +189,2391 @@ pub mod priority_queue {
         /// assert_eq!(queue.pop(), None);
         /// ```
         pub fn pop(&mut self) -> Option<T> {
-            self.list.pop()
+            if self.heap.is_empty() {
+                return None;
+            }
+            Some(self.remove_at(0))
         }
-    }
 
-    impl<T> Iterator for PriorityQueue<T>
-    where
-        T: Copy + PartialOrd,
-    {
-        type Item = T;
+        /// Get the smallest data in the queue, without removing it
+        ///
+        /// When the queue is empty, None is returned.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+        /// assert_eq!(queue.peek(), None);
+        /// queue.insert(2);
+        /// queue.insert(1);
+        /// assert_eq!(queue.peek(), Some(&1));
+        /// ```
+        pub fn peek(&self) -> Option<&T> {
+            self.heap.first()
+        }
 
-        fn next(&mut self) -> Option<Self::Item> {
-            self.list.next()
+        /// All data in the queue as a slice, in an unspecified order
+        ///
+        /// This is the array backing the heap, so no items are copied. Only
+        /// the first item is guaranteed to be in place, which is the one
+        /// returned by [`peek`].
+        ///
+        /// [`peek`]: #method.peek
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+        /// queue.extend(vec![3, 1, 2]);
+        /// let mut items = queue.as_slice().to_vec();
+        /// items.sort();
+        /// assert_eq!(items, vec![1, 2, 3]);
+        /// ```
+        pub fn as_slice(&self) -> &[T] {
+            &self.heap
         }
-    }
 
-    // Helper function for inserting items in order in the LinkedList
-    fn insert_inorder<T: Copy + PartialOrd>(ll: &mut LinkedList<T>, data: T) {
-        match ll.0 {
-            None => ll.append(data),
-            Some((it, ref mut child)) => {
-                if data >= it {
-                    insert_inorder(child, data)
-                } else {
-                    ll.insert_here(data)
+        /// Get the first data in the queue, inserting `default()` if it is empty
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+        /// assert_eq!(queue.peek_or_insert_with(|| 5), 5);
+        /// queue.insert(1);
+        /// assert_eq!(queue.peek_or_insert_with(|| 5), 1);
+        /// ```
+        pub fn peek_or_insert_with<F>(&mut self, default: F) -> T
+        where
+            F: FnOnce() -> T,
+        {
+            match self.peek() {
+                Some(&data) => data,
+                None => {
+                    let data = default();
+                    self.insert(data);
+                    data
                 }
             }
         }
-    }
-
-    #[cfg(test)]
-    mod tests {
-        use super::*;
 
-        #[test]
-        fn init_test() {
-            let queue: PriorityQueue<i32> = PriorityQueue::new();
-            assert!(queue.list.peek().is_none());
+        /// Remove the smallest data from the queue and move it into `out`
+        ///
+        /// Returns false when the queue is empty, in which case `out` is left
+        /// unchanged.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+        /// queue.insert(2);
+        ///
+        /// let mut slot = 0;
+        /// assert!(queue.pop_into(&mut slot));
+        /// assert_eq!(slot, 2);
+        /// assert!(!queue.pop_into(&mut slot));
+        /// assert_eq!(slot, 2);
+        /// ```
+        pub fn pop_into(&mut self, out: &mut T) -> bool {
+            match self.pop() {
+                Some(data) => {
+                    *out = data;
+                    true
+                }
+                None => false,
+            }
         }
 
-        #[test]
-        fn insert_test() {
-            let mut queue = PriorityQueue::new();
-            queue.insert(1);
-            assert_eq!(queue.list.peek(), Some(1));
+        /// Move all data in increasing order to the end of `out`
+        ///
+        /// The queue is left empty, and the existing allocation of `out` is
+        /// reused, which is useful when draining into the same buffer
+        /// repeatedly.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+        /// queue.insert(3);
+        /// queue.insert(1);
+        ///
+        /// let mut out = vec![9];
+        /// queue.drain_sorted_into(&mut out);
+        /// assert_eq!(out, vec![9, 1, 3]);
+        /// assert_eq!(queue.pop(), None);
+        /// ```
+        pub fn drain_sorted_into(&mut self, out: &mut Vec<T>) {
+            while let Some(data) = self.pop() {
+                out.push(data);
+            }
         }
 
-        #[test]
-        fn insert_order_test() {
-            let mut queue = PriorityQueue::new();
-            queue.insert(1);
-            queue.insert(3);
-            queue.insert(2);
-            assert_eq!(queue.pop(), Some(1));
-            assert_eq!(queue.pop(), Some(2));
-            assert_eq!(queue.pop(), Some(3));
-            assert_eq!(queue.pop(), None);
+        /// Consume the queue, returning all data in the order it would be popped
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+        /// queue.insert(3);
+        /// queue.insert(1);
+        /// queue.insert(2);
+        /// assert_eq!(queue.into_sorted_vec(), vec![1, 2, 3]);
+        /// ```
+        pub fn into_sorted_vec(mut self) -> Vec<T> {
+            let mut out = Vec::with_capacity(self.len());
+            self.drain_sorted_into(&mut out);
+            out
         }
-    }
-}
 
-pub mod queue {
-    /// The default capacity a queue gets when it is initialized
-    const DEFAULT_INIT_QUEUE_CAPACITY: usize = 32;
+        /// Remove all data from the queue, in the order it would be popped
+        ///
+        /// Unlike iterating over the queue itself, the queue is only borrowed
+        /// and can be reused afterwards. It is empty as soon as `drain` is
+        /// called, even when the iterator is not used up.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+        /// queue.insert(2);
+        /// queue.insert(1);
+        /// assert_eq!(queue.drain().collect::<Vec<i32>>(), vec![1, 2]);
+        /// assert!(queue.is_empty());
+        /// ```
+        pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+            let heap = std::mem::take(&mut self.heap);
+            let seqs = std::mem::take(&mut self.seqs);
+            self.borrowing(heap, seqs)
+        }
 
-    #[derive(Debug)]
-    pub struct Queue<T> {
-        list: Vec<T>,
-        head: usize,
-        tail: usize,
-    }
+        /// Remove all items matching `pred`, returning them in increasing order
+        ///
+        /// The items can be anywhere in the queue, not only at the front. The
+        /// remaining items stay where they are, after which the order of the
+        /// queue is restored once (see [`rebuild`]).
+        ///
+        /// [`rebuild`]: #method.rebuild
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+        /// queue.extend(vec![3, 1, 2]);
+        /// assert_eq!(queue.drain_filter(|x| *x >= 2), vec![2, 3]);
+        /// assert_eq!(queue.pop(), Some(1));
+        /// assert_eq!(queue.pop(), None);
+        /// ```
+        pub fn drain_filter<F>(&mut self, mut pred: F) -> Vec<T>
+        where
+            F: FnMut(&T) -> bool,
+        {
+            let mut heap = Vec::new();
+            let mut seqs = Vec::new();
+            // move the remaining items to the front, like Vec::retain
+            let mut kept = 0;
+            for i in 0..self.heap.len() {
+                if pred(&self.heap[i]) {
+                    heap.push(self.heap[i]);
+                    seqs.push(self.seqs[i]);
+                } else {
+                    self.heap[kept] = self.heap[i];
+                    self.seqs[kept] = self.seqs[i];
+                    kept += 1;
+                }
+            }
+            self.heap.truncate(kept);
+            self.seqs.truncate(kept);
+            self.rebuild();
 
-    impl<T> Queue<T> {
-        pub fn new() -> Self {
-            Queue::with_capacity(DEFAULT_INIT_QUEUE_CAPACITY)
+            let mut drained = self.borrowing(heap, seqs);
+            drained.rebuild();
+            drained.collect()
         }
 
-        /// Initialize a Queue with a custom capacity
+        /// The number of items in the queue which are ordered before `value`
         ///
-        /// This is mostly useful if you know for certain the queue is going to
-        /// get large, or remain (very) small.
-        pub fn with_capacity(capacity: usize) -> Self {
-            Self {
-                list: Vec::with_capacity(capacity),
-                head: 0,
-                tail: 0,
-            }
+        /// This is the position `value` would have among the items in the
+        /// order of the queue, which is useful for percentile queries. The
+        /// queue is not changed.
+        ///
+        /// ```
+        /// use data_structures::queues::priority_queue::PriorityQueue;
+        ///
+        /// let mut queue = PriorityQueue::new();
+        /// queue.extend(vec![1, 2, 3, 4]);
+        /// assert_eq!(queue.rank(&3), 2);
+        ///
+        /// let mut queue = PriorityQueue::new_max();
+        /// queue.extend(vec![1, 2, 3, 4]);
+        /// assert_eq!(queue.rank(&3), 1);
+        /// ```
+        pub fn rank(&self, value: &T) -> usize {
+            self.heap
+                .iter()
+                .filter(|data| (self.compare)(data, value).is_lt())
+                .count()
         }
 
-        /// Adds an item to the queue (FIFO)
+        /// The number of items in the queue
         ///
-        /// The data is moved into the queue, so clone/copy if you need it.
+        /// ```
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+        /// queue.extend(vec![1, 2]);
+        /// assert_eq!(queue.len(), 2);
+        /// ```
+        pub fn len(&self) -> usize {
+            self.heap.len()
+        }
+
+        /// Checks if there are no items in the queue
         ///
         /// ```
-        /// let mut queue = data_structures::queues::queue::Queue::new();
-        /// queue.enqueue(1);
-        /// queue.enqueue(2);
-        /// queue.enqueue(3);
-        /// assert_eq!(queue.dequeue(), Some(1));
-        /// assert_eq!(queue.dequeue(), Some(2));
-        /// assert_eq!(queue.dequeue(), Some(3));
-        /// assert_eq!(queue.dequeue(), None);
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+        /// assert!(queue.is_empty());
+        /// queue.insert(1);
+        /// assert!(!queue.is_empty());
         /// ```
-        pub fn enqueue(&mut self, data: T) {
-            if !self.has_space() {
-                self.resize();
-            }
-            // self.list.insert(self.tail, data);
-            if self.list.len() > self.tail {
-                self.list[self.tail] = data;
-            } else {
-                self.list.insert(self.list.len(), data);
-            }
-            self.incr_tail();
+        pub fn is_empty(&self) -> bool {
+            self.heap.is_empty()
         }
 
-        /// Removes an item from the queue (FIFO)
+        /// The number of items the queue can hold without allocating
         ///
-        /// Returns `None` if the queue is empty
+        /// This is the capacity of the `Vec` backing the heap, which grows like
+        /// any other `Vec`.
         ///
         /// ```
-        /// let mut queue = data_structures::queues::queue::Queue::new();
-        /// queue.enqueue(1);
-        /// queue.enqueue(2);
-        /// queue.enqueue(3);
-        /// assert_eq!(queue.dequeue(), Some(1));
-        /// assert_eq!(queue.dequeue(), Some(2));
-        /// assert_eq!(queue.dequeue(), Some(3));
-        /// assert_eq!(queue.dequeue(), None);
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+        /// queue.insert(1);
+        /// assert!(queue.capacity() >= 1);
         /// ```
-        pub fn dequeue(&mut self) -> Option<T> {
-            if self.empty() {
-                None
-            } else {
-                let dummy = unsafe {
-                    // We swap the item at head with a zero value of type T
-                    let mut dummy = std::mem::zeroed();
-                    let it = self.list.get_unchecked_mut(self.head);
-                    std::mem::swap(it, &mut dummy);
-                    dummy
-                };
-                self.incr_head();
-                Some(dummy)
-            }
+        pub fn capacity(&self) -> usize {
+            self.heap.capacity()
         }
 
-        /// Checks if there are items in the queue
+        /// Apply `f` to every item in the queue
+        ///
+        /// The order of the queue is restored once after all items have been
+        /// modified (see [`rebuild`]), instead of re-inserting each item.
+        ///
+        /// [`rebuild`]: #method.rebuild
         ///
         /// ```
-        /// let mut queue = data_structures::queues::queue::Queue::new();
-        /// assert!(queue.empty());
-        /// queue.enqueue(1);
-        /// assert!(!queue.empty());
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+        /// queue.insert(1);
+        /// queue.insert(2);
+        /// queue.map_all(|x| *x = 10 - *x);
+        /// assert_eq!(queue.pop(), Some(8));
+        /// assert_eq!(queue.pop(), Some(9));
         /// ```
-        pub fn empty(&self) -> bool {
-            self.head == self.tail
+        pub fn map_all<F>(&mut self, mut f: F)
+        where
+            F: FnMut(&mut T),
+        {
+            self.heap.iter_mut().for_each(&mut f);
+            self.rebuild();
         }
 
-        /// The number of items in the queue
+        /// Restore the order of the queue from scratch
+        ///
+        /// All items are put back in heap order at once, in `O(n)` time, which
+        /// is needed after the items have been modified in place.
         ///
         /// ```
-        /// let mut queue = data_structures::queues::queue::Queue::new();
-        /// queue.enqueue(1);
-        /// queue.enqueue(1);
-        /// queue.enqueue(1);
-        /// assert_eq!(queue.len(), 3);
+        /// let mut queue = data_structures::queues::priority_queue::PriorityQueue::new();
+        /// queue.insert(2);
+        /// queue.insert(1);
+        /// queue.rebuild();
+        /// assert_eq!(queue.pop(), Some(1));
+        /// assert_eq!(queue.pop(), Some(2));
         /// ```
-        pub fn len(&self) -> usize {
-            if self.head > self.tail {
-                self.list.capacity() - self.head + self.tail
-            } else {
-                self.tail - self.head
+        pub fn rebuild(&mut self) {
+            for i in (0..self.heap.len() / 2).rev() {
+                self.sift_down(i);
+            }
+        }
+
+        // Remove the item at position i of the heap, filling the gap with the
+        // last item and moving that to its place
+        fn remove_at(&mut self, i: usize) -> T {
+            let data = self.heap.swap_remove(i);
+            self.seqs.swap_remove(i);
+            if i < self.heap.len() {
+                self.sift_down(i);
+                self.sift_up(i);
+            }
+            data
+        }
+
+        // Move the item at position i up, until its parent is not after it
+        fn sift_up(&mut self, mut i: usize) {
+            while i > 0 {
+                let parent = (i - 1) / 2;
+                if !self.order(i, parent).is_lt() {
+                    break;
+                }
+                self.swap(i, parent);
+                i = parent;
+            }
+        }
+
+        // Move the item at position i down, until none of its children are
+        // before it
+        fn sift_down(&mut self, mut i: usize) {
+            let len = self.heap.len();
+            loop {
+                let left = 2 * i + 1;
+                if left >= len {
+                    break;
+                }
+                let right = left + 1;
+                let child = if right < len && self.order(right, left).is_lt() {
+                    right
+                } else {
+                    left
+                };
+                if !self.order(child, i).is_lt() {
+                    break;
+                }
+                self.swap(i, child);
+                i = child;
+            }
+        }
+
+        // Mutable references to all items, in no particular order, for tests
+        // which break the order of the queue on purpose
+        #[cfg(test)]
+        fn items_mut(&mut self) -> impl Iterator<Item = &mut T> {
+            self.heap.iter_mut()
+        }
+
+        // All items in the order they would be popped, by popping a copy
+        fn sorted(&self) -> PriorityQueue<T, &C> {
+            self.borrowing(self.heap.clone(), self.seqs.clone())
+        }
+
+        // A queue holding the given heap, ordered by the comparator of this
+        // queue, which pops its items without changing this queue
+        fn borrowing(&self, heap: Vec<T>, seqs: Vec<usize>) -> PriorityQueue<T, &C> {
+            PriorityQueue {
+                heap,
+                seqs,
+                compare: &self.compare,
+                seq: self.seq,
             }
         }
 
-        // private helper functions
+        // The order of the items at positions i and j of the heap, where equal
+        // items are ordered by their sequence number
+        fn order(&self, i: usize, j: usize) -> Ordering {
+            (self.compare)(&self.heap[i], &self.heap[j]).then(self.seqs[i].cmp(&self.seqs[j]))
+        }
+
+        // Swap the items at positions i and j of the heap
+        fn swap(&mut self, i: usize, j: usize) {
+            self.heap.swap(i, j);
+            self.seqs.swap(i, j);
+        }
+    }
+
+    impl<T, C> Iterator for PriorityQueue<T, C>
+    where
+        T: Copy,
+        C: Fn(&T, &T) -> Ordering,
+    {
+        type Item = T;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.pop()
+        }
+    }
+
+    impl<T, C> PartialEq for PriorityQueue<T, C>
+    where
+        T: Copy + PartialEq,
+        C: Fn(&T, &T) -> Ordering + Clone,
+    {
+        /// Queues are equal when they would pop the same items in the same order
+        fn eq(&self, other: &Self) -> bool {
+            self.clone().eq(other.clone())
+        }
+    }
+
+    impl<T, C> Extend<T> for PriorityQueue<T, C>
+    where
+        T: Copy,
+        C: Fn(&T, &T) -> Ordering,
+    {
+        /// Insert all items of the iterator, reserving room from the size hint
+        fn extend<I>(&mut self, iter: I)
+        where
+            I: IntoIterator<Item = T>,
+        {
+            let iter = iter.into_iter();
+            self.heap.reserve(iter.size_hint().0);
+            self.seqs.reserve(iter.size_hint().0);
+            iter.for_each(|data| self.insert(data));
+        }
+    }
+
+    impl<T, C> std::fmt::Debug for PriorityQueue<T, C>
+    where
+        T: Copy + std::fmt::Debug,
+        C: Fn(&T, &T) -> Ordering,
+    {
+        /// Shows the items in the order they would be popped
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_list().entries(self.sorted()).finish()
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<T, C> serde::Serialize for PriorityQueue<T, C>
+    where
+        T: Copy + serde::Serialize,
+        C: Fn(&T, &T) -> Ordering,
+    {
+        /// Serializes the items as a sequence, in the order they would be popped
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.collect_seq(self.sorted())
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de, T> serde::Deserialize<'de> for PriorityQueue<T>
+    where
+        T: Copy + PartialOrd + serde::Deserialize<'de>,
+    {
+        /// Deserializes a sequence of items, inserting them with the default order
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            Vec::deserialize(deserializer).map(PriorityQueue::from_iter)
+        }
+    }
+
+    impl<T> FromIterator<T> for PriorityQueue<T>
+    where
+        T: Copy + PartialOrd,
+    {
+        /// Insert all items into a new queue with the default order
+        fn from_iter<I>(iter: I) -> Self
+        where
+            I: IntoIterator<Item = T>,
+        {
+            let mut queue = PriorityQueue::new();
+            queue.extend(iter);
+            queue
+        }
+    }
+
+    // The default order of the queue, for items with a partial order
+    fn partial_compare<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+        a.partial_cmp(b).unwrap_or(Ordering::Equal)
+    }
+
+    // The reverse of the default order, for a queue which pops the largest first
+    fn reverse_partial_compare<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+        partial_compare(b, a)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn init_test() {
+            let queue: PriorityQueue<i32> = PriorityQueue::new();
+            assert!(queue.heap.is_empty());
+        }
+
+        #[test]
+        fn default_test() {
+            let mut queue: PriorityQueue<i32> = Default::default();
+            assert!(queue.heap.is_empty());
+            assert_eq!(queue.pop(), None);
+        }
+
+        #[test]
+        fn insert_test() {
+            let mut queue = PriorityQueue::new();
+            queue.insert(1);
+            assert_eq!(queue.heap.first(), Some(&1));
+        }
+
+        #[test]
+        fn insert_order_test() {
+            let mut queue = PriorityQueue::new();
+            queue.insert(1);
+            queue.insert(3);
+            queue.insert(2);
+            assert_eq!(queue.pop(), Some(1));
+            assert_eq!(queue.pop(), Some(2));
+            assert_eq!(queue.pop(), Some(3));
+            assert_eq!(queue.pop(), None);
+        }
+
+        #[test]
+        fn upsert_test() {
+            let mut queue = PriorityQueue::new();
+            queue.extend(vec![(1, 'a'), (2, 'b'), (3, 'c')]);
+
+            // update an existing item, moving it to the back
+            queue.upsert(|x| x.1 == 'a', (4, 'a'));
+            // insert a new item
+            queue.upsert(|x| x.1 == 'd', (0, 'd'));
+            assert_eq!(queue.pop(), Some((0, 'd')));
+            assert_eq!(queue.pop(), Some((2, 'b')));
+            assert_eq!(queue.pop(), Some((3, 'c')));
+            assert_eq!(queue.pop(), Some((4, 'a')));
+            assert_eq!(queue.pop(), None);
+        }
+
+        #[test]
+        fn reverse_order_test() {
+            use std::cmp::Reverse;
+
+            let mut queue = PriorityQueue::new();
+            queue.insert(Reverse(1));
+            queue.insert(Reverse(3));
+            queue.insert(Reverse(2));
+            assert_eq!(queue.pop(), Some(Reverse(3)));
+            assert_eq!(queue.pop(), Some(Reverse(2)));
+            assert_eq!(queue.pop(), Some(Reverse(1)));
+            assert_eq!(queue.pop(), None);
+        }
+
+        #[test]
+        fn new_max_test() {
+            let mut min = PriorityQueue::new();
+            let mut max = PriorityQueue::new_max();
+            for i in [2, 1, 3] {
+                min.insert(i);
+                max.insert(i);
+            }
+            assert_eq!(min.collect::<Vec<i32>>(), vec![1, 2, 3]);
+            assert_eq!(max.collect::<Vec<i32>>(), vec![3, 2, 1]);
+
+            let mut max: PriorityQueue<f64> = PriorityQueue::new_max();
+            max.extend(vec![0.5, 2.5, 1.5]);
+            assert_eq!(max.peek(), Some(&2.5));
+        }
+
+        #[test]
+        fn comparator_test() {
+            // handles into a table of priorities, without an order of their own
+            #[derive(Clone, Copy, PartialEq, Debug)]
+            struct Handle(usize);
+
+            let priorities = [30, 10, 20];
+            let mut queue = PriorityQueue::with_comparator(|a: &Handle, b: &Handle| {
+                priorities[a.0].cmp(&priorities[b.0])
+            });
+            queue.insert(Handle(0));
+            queue.insert(Handle(1));
+            queue.insert(Handle(2));
+            queue.extend(vec![Handle(1)]);
+            assert_eq!(queue.pop(), Some(Handle(1)));
+            assert_eq!(queue.pop(), Some(Handle(1)));
+            assert_eq!(queue.pop(), Some(Handle(2)));
+            assert_eq!(queue.pop(), Some(Handle(0)));
+            assert_eq!(queue.pop(), None);
+        }
+
+        #[test]
+        fn peek_test() {
+            let mut queue = PriorityQueue::new();
+            assert_eq!(queue.peek(), None);
+            queue.insert(3);
+            queue.insert(1);
+            queue.insert(2);
+            assert_eq!(queue.peek(), Some(&1));
+            assert_eq!(queue.peek(), Some(&1));
+            assert_eq!(queue.pop(), Some(1));
+            assert_eq!(queue.peek(), Some(&2));
+        }
+
+        #[test]
+        fn comparator_field_test() {
+            // the derived order compares the id first
+            #[derive(Clone, Copy, PartialEq, PartialOrd, Debug)]
+            struct Task {
+                id: u32,
+                deadline: u32,
+            }
+
+            let tasks = [
+                Task {
+                    id: 1,
+                    deadline: 30,
+                },
+                Task {
+                    id: 2,
+                    deadline: 10,
+                },
+                Task {
+                    id: 3,
+                    deadline: 20,
+                },
+            ];
+            let mut by_id = PriorityQueue::new();
+            let mut by_deadline =
+                PriorityQueue::with_comparator(|a: &Task, b: &Task| a.deadline.cmp(&b.deadline));
+            by_id.extend(tasks);
+            by_deadline.extend(tasks);
+
+            let ids: Vec<u32> = by_id.map(|task| task.id).collect();
+            assert_eq!(ids, vec![1, 2, 3]);
+            let ids: Vec<u32> = by_deadline.map(|task| task.id).collect();
+            assert_eq!(ids, vec![2, 3, 1]);
+        }
+
+        #[test]
+        fn as_slice_test() {
+            let mut queue = PriorityQueue::new();
+            assert!(queue.as_slice().is_empty());
+
+            let items = vec![5, 3, 8, 1, 9, 2, 7, 3];
+            queue.extend(items.clone());
+            assert_eq!(queue.as_slice().len(), queue.len());
+            assert_eq!(queue.as_slice().first(), queue.peek());
+            let mut slice = queue.as_slice().to_vec();
+            slice.sort();
+            let mut expected = items;
+            expected.sort();
+            assert_eq!(slice, expected);
+
+            queue.pop();
+            assert_eq!(queue.as_slice().len(), queue.len());
+            assert!(!queue.as_slice().contains(&1));
+        }
+
+        #[test]
+        fn peek_or_insert_with_test() {
+            let mut queue = PriorityQueue::new();
+            assert_eq!(queue.peek_or_insert_with(|| 3), 3);
+            assert_eq!(queue.peek_or_insert_with(|| panic!("not empty")), 3);
+            queue.insert(2);
+            assert_eq!(queue.peek_or_insert_with(|| 4), 2);
+            assert_eq!(queue.pop(), Some(2));
+            assert_eq!(queue.pop(), Some(3));
+            assert_eq!(queue.pop(), None);
+        }
+
+        #[test]
+        fn pop_into_test() {
+            let mut queue = PriorityQueue::new();
+            queue.insert(3);
+            queue.insert(1);
+            queue.insert(2);
+
+            let mut slot = 0;
+            let mut popped = Vec::new();
+            while queue.pop_into(&mut slot) {
+                popped.push(slot);
+            }
+            assert_eq!(popped, vec![1, 2, 3]);
+            assert_eq!(slot, 3);
+        }
+
+        #[test]
+        fn drain_sorted_into_test() {
+            let mut queue = PriorityQueue::new();
+            queue.insert(4);
+            queue.insert(2);
+            queue.insert(3);
+
+            let mut out = vec![7, 0];
+            queue.drain_sorted_into(&mut out);
+            assert_eq!(out, vec![7, 0, 2, 3, 4]);
+            assert!(queue.heap.is_empty());
+        }
+
+        #[test]
+        fn merge_test() {
+            let mut queue = PriorityQueue::new();
+            queue.extend(vec![5, 1, 9, 3]);
+            let mut other = PriorityQueue::new();
+            other.extend(vec![4, 8, 2, 6, 3]);
+            queue.merge(other);
+            assert_eq!(queue.len(), 9);
+            assert_eq!(queue.into_sorted_vec(), vec![1, 2, 3, 3, 4, 5, 6, 8, 9]);
+
+            // into an empty queue
+            let mut queue = PriorityQueue::new();
+            let mut other = PriorityQueue::new();
+            other.extend(vec![2, 3, 1]);
+            queue.merge(other);
+            assert_eq!(queue.into_sorted_vec(), vec![1, 2, 3]);
+
+            // an empty queue in
+            let mut queue = PriorityQueue::new();
+            queue.extend(vec![2, 3, 1]);
+            queue.merge(PriorityQueue::new());
+            assert_eq!(queue.into_sorted_vec(), vec![1, 2, 3]);
+        }
+
+        #[test]
+        fn merge_stable_test() {
+            let compare = |a: &(i32, char), b: &(i32, char)| a.0.cmp(&b.0);
+            let mut queue = PriorityQueue::with_comparator(compare);
+            queue.insert((1, 'a'));
+            queue.insert((1, 'b'));
+            let mut other = PriorityQueue::with_comparator(compare);
+            other.insert((1, 'c'));
+            other.insert((0, 'x'));
+            other.insert((1, 'd'));
+            queue.merge(other);
+            queue.insert((1, 'e'));
+            let items: Vec<char> = queue.map(|item| item.1).collect();
+            assert_eq!(items, vec!['x', 'a', 'b', 'c', 'd', 'e']);
+        }
+
+        // A queue of floats with NaN in between, which have no total order
+        fn nan_queue() -> PriorityQueue<f64> {
+            (0..50)
+                .map(|i| {
+                    if i % 3 == 0 {
+                        f64::NAN
+                    } else {
+                        (i * 37 % 101) as f64
+                    }
+                })
+                .collect()
+        }
+
+        #[test]
+        fn into_sorted_vec_nan_test() {
+            let items = nan_queue().into_sorted_vec();
+            assert_eq!(items.len(), 50);
+            assert_eq!(items.iter().filter(|x| x.is_nan()).count(), 17);
+        }
+
+        #[test]
+        fn into_sorted_vec_test() {
+            let mut queue = PriorityQueue::new();
+            queue.extend(vec![5, 3, 8, 1, 9, 2, 7]);
+            assert_eq!(queue.into_sorted_vec(), vec![1, 2, 3, 5, 7, 8, 9]);
+
+            let mut queue = PriorityQueue::new_max();
+            queue.extend(vec![2, 3, 1]);
+            assert_eq!(queue.into_sorted_vec(), vec![3, 2, 1]);
+
+            let empty: PriorityQueue<i32> = PriorityQueue::new();
+            assert!(empty.into_sorted_vec().is_empty());
+        }
+
+        #[test]
+        fn drain_test() {
+            let mut queue = PriorityQueue::new();
+            queue.extend(vec![4, 1, 3, 2]);
+            let drained: Vec<i32> = queue.drain().collect();
+            assert_eq!(drained, vec![1, 2, 3, 4]);
+            assert!(queue.is_empty());
+            assert_eq!(queue.pop(), None);
+
+            // the queue can be reused
+            queue.insert(6);
+            queue.insert(5);
+            assert_eq!(queue.pop(), Some(5));
+            assert_eq!(queue.pop(), Some(6));
+
+            // dropping the iterator early still empties the queue
+            queue.extend(vec![2, 1, 3]);
+            assert_eq!(queue.drain().next(), Some(1));
+            assert!(queue.is_empty());
+            assert_eq!(queue.drain().next(), None);
+        }
+
+        #[test]
+        fn drain_nan_test() {
+            let mut queue = nan_queue();
+            assert_eq!(queue.drain().count(), 50);
+            assert!(queue.is_empty());
+        }
+
+        #[test]
+        fn drain_filter_nan_test() {
+            let mut queue = nan_queue();
+            let drained = queue.drain_filter(|x| x.is_nan());
+            assert_eq!(drained.len(), 17);
+            assert!(drained.iter().all(|x| x.is_nan()));
+            let items = queue.into_sorted_vec();
+            assert_eq!(items.len(), 33);
+            assert!(items.windows(2).all(|pair| pair[0] <= pair[1]));
+
+            let mut queue = nan_queue();
+            assert_eq!(queue.drain_filter(|x| *x < 50.0).len(), 15);
+            assert_eq!(queue.len(), 35);
+        }
+
+        #[test]
+        fn drain_filter_stable_test() {
+            let mut queue =
+                PriorityQueue::with_comparator(|a: &(i32, char), b: &(i32, char)| a.0.cmp(&b.0));
+            queue.extend(vec![
+                (1, 'a'),
+                (0, 'x'),
+                (1, 'b'),
+                (2, 'y'),
+                (1, 'c'),
+                (0, 'z'),
+            ]);
+            assert_eq!(
+                queue.drain_filter(|x| x.1 < 'x'),
+                vec![(1, 'a'), (1, 'b'), (1, 'c')]
+            );
+            let items: Vec<char> = queue.map(|x| x.1).collect();
+            assert_eq!(items, vec!['x', 'z', 'y']);
+        }
+
+        #[test]
+        fn drain_filter_test() {
+            let mut queue = PriorityQueue::new();
+            queue.extend(vec![4, 1, 6, 3, 2, 5]);
+            assert_eq!(queue.drain_filter(|x| x % 2 == 0), vec![2, 4, 6]);
+            assert_eq!(queue.pop(), Some(1));
+            assert_eq!(queue.pop(), Some(3));
+            assert_eq!(queue.pop(), Some(5));
+            assert_eq!(queue.pop(), None);
+
+            let mut queue = PriorityQueue::new();
+            queue.extend(vec![1, 2]);
+            assert!(queue.drain_filter(|_| false).is_empty());
+            assert_eq!(queue.drain_filter(|_| true), vec![1, 2]);
+            assert!(queue.heap.is_empty());
+        }
+
+        #[test]
+        fn rebuild_test() {
+            let mut queue = PriorityQueue::new();
+            queue.insert(1);
+            queue.insert(2);
+            queue.insert(3);
+            queue.insert(4);
+            // break the ordering of the heap on purpose
+            for (data, value) in queue.items_mut().zip([4, 3, 2, 1]) {
+                *data = value;
+            }
+            assert_eq!(queue.peek(), Some(&4));
+            queue.rebuild();
+            assert_eq!(queue.pop(), Some(1));
+            assert_eq!(queue.pop(), Some(2));
+            assert_eq!(queue.pop(), Some(3));
+            assert_eq!(queue.pop(), Some(4));
+            assert_eq!(queue.pop(), None);
+        }
+
+        #[test]
+        fn eq_test() {
+            let mut a = PriorityQueue::new();
+            a.insert(1);
+            a.insert(2);
+            a.insert(3);
+            let mut b = PriorityQueue::new();
+            b.insert(3);
+            b.insert(1);
+            b.insert(2);
+            assert!(a == b);
+            assert_eq!(a.pop(), Some(1));
+            assert!(a != b);
+            b.pop();
+            assert!(a == b);
+
+            let empty: PriorityQueue<i32> = PriorityQueue::new();
+            assert!(empty == PriorityQueue::new());
+            assert!(empty != a);
+        }
+
+        #[test]
+        fn extend_test() {
+            let mut queue = PriorityQueue::new();
+            queue.insert(1);
+            queue.insert(2);
+            queue.insert(3);
+            queue.extend(vec![0, 4]);
+            for i in 0..5 {
+                assert_eq!(queue.pop(), Some(i));
+            }
+            assert_eq!(queue.pop(), None);
+        }
+
+        #[test]
+        fn from_iter_test() {
+            let mut queue: PriorityQueue<i32> = vec![5, 3, 8, 1, 9, 2].into_iter().collect();
+            assert_eq!(queue.len(), 6);
+            assert_eq!(queue.pop(), Some(1));
+            assert_eq!(queue.pop(), Some(2));
+
+            queue.extend(vec![7, 0, 4]);
+            assert_eq!(queue.into_sorted_vec(), vec![0, 3, 4, 5, 7, 8, 9]);
+
+            let empty: PriorityQueue<i32> = std::iter::empty().collect();
+            assert!(empty.is_empty());
+        }
+
+        #[test]
+        fn debug_test() {
+            let mut queue = PriorityQueue::new();
+            queue.extend(vec![3, 1, 2]);
+            assert_eq!(format!("{:?}", queue), "[1, 2, 3]");
+            let mut queue = PriorityQueue::new_max();
+            queue.extend(vec!['b', 'c', 'a']);
+            assert_eq!(format!("{:?}", queue), "['c', 'b', 'a']");
+            let empty: PriorityQueue<i32> = PriorityQueue::new();
+            assert_eq!(format!("{:?}", empty), "[]");
+
+            let queue = nan_queue();
+            assert_eq!(format!("{:?}", queue).matches("NaN").count(), 17);
+            assert_eq!(queue.len(), 50);
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn serde_test() {
+            let mut queue = PriorityQueue::new();
+            queue.extend(vec![3, 1, 2]);
+            let json = serde_json::to_string(&queue).unwrap();
+            assert_eq!(json, "[1,2,3]");
+            let back: PriorityQueue<i32> = serde_json::from_str(&json).unwrap();
+            assert!(back == queue);
+
+            // the order is restored when deserializing
+            let mut back: PriorityQueue<i32> = serde_json::from_str("[5,4,6]").unwrap();
+            assert_eq!(back.pop(), Some(4));
+            assert_eq!(back.pop(), Some(5));
+            assert_eq!(back.pop(), Some(6));
+
+            let empty: PriorityQueue<i32> = PriorityQueue::new();
+            let json = serde_json::to_string(&empty).unwrap();
+            assert_eq!(json, "[]");
+            let back: PriorityQueue<i32> = serde_json::from_str(&json).unwrap();
+            assert!(back.is_empty());
+
+            // JSON has no NaN, so it is written as null
+            let json = serde_json::to_string(&nan_queue()).unwrap();
+            assert_eq!(json.matches("null").count(), 17);
+            let back: PriorityQueue<Option<f64>> = serde_json::from_str(&json).unwrap();
+            assert_eq!(back.len(), 50);
+        }
+
+        #[test]
+        fn rank_test() {
+            let mut queue = PriorityQueue::new();
+            queue.extend(vec![4, 2, 3, 1]);
+            assert_eq!(queue.rank(&3), 2);
+            assert_eq!(queue.rank(&0), 0);
+            assert_eq!(queue.rank(&1), 0);
+            assert_eq!(queue.rank(&10), 4);
+            assert_eq!(queue.pop(), Some(1));
+            assert_eq!(queue.rank(&3), 1);
+
+            let empty: PriorityQueue<i32> = PriorityQueue::new();
+            assert_eq!(empty.rank(&3), 0);
+
+            // the rank follows the order of the queue
+            let mut queue = PriorityQueue::new_max();
+            queue.extend(vec![4, 2, 3, 1]);
+            assert_eq!(queue.rank(&3), 1);
+            assert_eq!(queue.rank(&4), 0);
+            assert_eq!(queue.rank(&0), 4);
+            assert_eq!(queue.pop(), Some(4));
+            assert_eq!(queue.rank(&3), 0);
+
+            let mut queue: PriorityQueue<f64> = PriorityQueue::new_max();
+            queue.extend(vec![0.5, 2.5, 1.5]);
+            assert_eq!(queue.rank(&1.0), 2);
+        }
+
+        #[test]
+        fn len_test() {
+            let mut queue = PriorityQueue::new();
+            assert!(queue.is_empty());
+            assert_eq!(queue.len(), 0);
+
+            queue.extend(vec![3, 1, 2]);
+            assert!(!queue.is_empty());
+            assert_eq!(queue.len(), 3);
+
+            while queue.pop().is_some() {}
+            assert!(queue.is_empty());
+            assert_eq!(queue.len(), 0);
+        }
+
+        #[test]
+        fn capacity_test() {
+            let mut queue = PriorityQueue::new();
+            assert_eq!(queue.capacity(), 0);
+            queue.insert(2);
+            queue.insert(1);
+            let capacity = queue.capacity();
+            assert!(capacity >= 2);
+            queue.insert(3);
+            assert!(queue.capacity() >= capacity);
+            assert!(queue.capacity() >= 3);
+        }
+
+        #[test]
+        fn map_all_test() {
+            let mut queue = PriorityQueue::new();
+            queue.insert(15);
+            queue.insert(10);
+            queue.insert(20);
+            queue.map_all(|x| *x -= 5);
+            assert_eq!(queue.pop(), Some(5));
+            assert_eq!(queue.pop(), Some(10));
+            assert_eq!(queue.pop(), Some(15));
+            assert_eq!(queue.pop(), None);
+
+            // a mapping which does not preserve the order
+            let mut queue = PriorityQueue::new();
+            queue.insert(1);
+            queue.insert(2);
+            queue.insert(3);
+            queue.map_all(|x| *x = -*x);
+            assert_eq!(queue.pop(), Some(-3));
+            assert_eq!(queue.pop(), Some(-2));
+            assert_eq!(queue.pop(), Some(-1));
+        }
+
+        #[test]
+        fn stable_test() {
+            // only the first field is the priority
+            let mut queue =
+                PriorityQueue::with_comparator(|a: &(i32, char), b: &(i32, char)| a.0.cmp(&b.0));
+            queue.insert((1, 'a'));
+            queue.insert((2, 'x'));
+            queue.insert((1, 'b'));
+            queue.insert((0, 'y'));
+            queue.insert((1, 'c'));
+            assert_eq!(queue.pop(), Some((0, 'y')));
+            assert_eq!(queue.pop(), Some((1, 'a')));
+            assert_eq!(queue.pop(), Some((1, 'b')));
+            assert_eq!(queue.pop(), Some((1, 'c')));
+            assert_eq!(queue.pop(), Some((2, 'x')));
+            assert_eq!(queue.pop(), None);
+
+            // many equal items, inserted between others
+            let mut queue =
+                PriorityQueue::with_comparator(|a: &(usize, usize), b: &(usize, usize)| {
+                    a.0.cmp(&b.0)
+                });
+            for i in 0..1000 {
+                queue.insert((i % 3, i));
+            }
+            let items: Vec<(usize, usize)> = queue.collect();
+            let mut expected: Vec<(usize, usize)> = (0..1000).map(|i| (i % 3, i)).collect();
+            expected.sort();
+            assert_eq!(items, expected);
+        }
+
+        #[test]
+        fn many_items_test() {
+            const N: usize = 50_000;
+            // multiplying by a number coprime to N shuffles 0..N
+            let mut queue = PriorityQueue::new();
+            for i in 0..N {
+                queue.insert((i * 7919) % N);
+            }
+            assert_eq!(queue.len(), N);
+            assert!(queue.eq(0..N));
+        }
+    }
+}
+
+pub mod queue {
+    /// The default capacity a queue gets when it is initialized
+    const DEFAULT_INIT_QUEUE_CAPACITY: usize = 32;
+
+    pub struct Queue<T> {
+        // every item is stored together with its sequence number, and slots
+        // without an item (outside head..tail) are None
+        list: Vec<Option<(usize, T)>>,
+        // size of the ring, which is always the length of list
+        cap: usize,
+        // the items are at head..tail (wrapping around), and as head == tail
+        // both when empty and when full, the number of items is kept apart
+        head: usize,
+        tail: usize,
+        count: usize,
+        // a bounded queue never grows when it is full
+        bounded: bool,
+        // sequence number of the next enqueued item
+        seq: usize,
+    }
+
+    impl<T> Queue<T> {
+        pub fn new() -> Self {
+            Queue::with_capacity(DEFAULT_INIT_QUEUE_CAPACITY)
+        }
+
+        /// Initialize a Queue with a custom capacity
+        ///
+        /// This is mostly useful if you know for certain the queue is going to
+        /// get large, or remain (very) small.
+        pub fn with_capacity(capacity: usize) -> Self {
+            Self {
+                list: empty_slots(capacity),
+                cap: capacity,
+                head: 0,
+                tail: 0,
+                count: 0,
+                bounded: false,
+                seq: 0,
+            }
+        }
+
+        /// Initialize a Queue which never grows beyond `capacity` items
+        ///
+        /// When a bounded queue is full, [`enqueue`] gives the item back and
+        /// [`enqueue_overwrite`] replaces the oldest item. The queue only
+        /// grows through an explicit [`reserve`].
+        ///
+        /// [`enqueue_overwrite`]: #method.enqueue_overwrite
+        /// [`enqueue`]: #method.enqueue
+        /// [`reserve`]: #method.reserve
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::bounded(1);
+        /// assert_eq!(queue.enqueue(1), Ok(()));
+        /// assert_eq!(queue.enqueue(2), Err(2));
+        /// assert_eq!(queue.capacity(), 1);
+        /// ```
+        pub fn bounded(capacity: usize) -> Self {
+            Self {
+                bounded: true,
+                ..Queue::with_capacity(capacity)
+            }
+        }
+
+        /// Create a queue from an iterator of results
+        ///
+        /// The items are enqueued in order, until the first error is found,
+        /// which is then returned instead of the queue.
+        ///
+        /// ```
+        /// use data_structures::queues::queue::Queue;
+        ///
+        /// let items: Vec<Result<i32, &str>> = vec![Ok(1), Ok(2)];
+        /// let mut queue = Queue::try_from_iter(items).unwrap();
+        /// assert_eq!(queue.dequeue(), Some(1));
+        ///
+        /// let items = vec![Ok(1), Err("bad item"), Ok(3)];
+        /// assert_eq!(Queue::try_from_iter(items).err(), Some("bad item"));
+        /// ```
+        pub fn try_from_iter<E, I>(iter: I) -> Result<Self, E>
+        where
+            I: IntoIterator<Item = Result<T, E>>,
+        {
+            let mut queue = Queue::new();
+            for item in iter {
+                // an unbounded queue always has space
+                let _ = queue.enqueue(item?);
+            }
+            Ok(queue)
+        }
+
+        /// Adds an item to the queue (FIFO)
+        ///
+        /// The data is moved into the queue, so clone/copy if you need it.
+        /// When the queue is [`bounded`] and full, the data is given back
+        /// as the error instead.
+        ///
+        /// [`bounded`]: #method.bounded
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(2).unwrap();
+        /// queue.enqueue(3).unwrap();
+        /// assert_eq!(queue.dequeue(), Some(1));
+        /// assert_eq!(queue.dequeue(), Some(2));
+        /// assert_eq!(queue.dequeue(), Some(3));
+        /// assert_eq!(queue.dequeue(), None);
+        ///
+        /// let mut queue = data_structures::queues::queue::Queue::bounded(1);
+        /// assert_eq!(queue.enqueue(1), Ok(()));
+        /// assert_eq!(queue.enqueue(2), Err(2));
+        /// ```
+        pub fn enqueue(&mut self, data: T) -> Result<(), T> {
+            self.enqueue_tracked(data).map(|_| ())
+        }
+
+        /// Adds an item to the queue (FIFO), returning its sequence number
+        ///
+        /// Every enqueued item gets a sequence number, which increases with
+        /// each enqueue. It can be used as a handle to find the item again
+        /// with [`get_by_seq`], as long as it is still in the queue.
+        ///
+        /// When the queue is [`bounded`] and full, the data is given back
+        /// as the error instead.
+        ///
+        /// [`get_by_seq`]: #method.get_by_seq
+        /// [`bounded`]: #method.bounded
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(1).unwrap();
+        /// let seq = queue.enqueue_tracked(2).unwrap();
+        /// queue.dequeue();
+        /// assert_eq!(queue.get_by_seq(seq), Some(&2));
+        /// ```
+        pub fn enqueue_tracked(&mut self, data: T) -> Result<usize, T> {
+            if !self.make_space() {
+                return Err(data);
+            }
+            let seq = self.seq;
+            self.seq += 1;
+            self.list[self.tail] = Some((seq, data));
+            self.incr_tail();
+            self.count += 1;
+            Ok(seq)
+        }
+
+        /// Adds an item to the front of the queue, so it is dequeued first
+        ///
+        /// Together with [`dequeue_back`], this makes the queue usable as a
+        /// double-ended queue. The item gets a new sequence number, like with
+        /// [`enqueue_tracked`]. Like with [`enqueue`], the data is given back
+        /// when the queue is bounded and full.
+        ///
+        /// [`dequeue_back`]: #method.dequeue_back
+        /// [`enqueue_tracked`]: #method.enqueue_tracked
+        /// [`enqueue`]: #method.enqueue
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(2).unwrap();
+        /// queue.enqueue_front(1).unwrap();
+        /// assert_eq!(queue.dequeue(), Some(1));
+        /// assert_eq!(queue.dequeue(), Some(2));
+        /// ```
+        pub fn enqueue_front(&mut self, data: T) -> Result<(), T> {
+            if !self.make_space() {
+                return Err(data);
+            }
+            let seq = self.seq;
+            self.seq += 1;
+            self.decr_head();
+            self.list[self.head] = Some((seq, data));
+            self.count += 1;
+            Ok(())
+        }
+
+        /// Adds an item to the queue (FIFO), removing the oldest item when full
+        ///
+        /// The queue never grows this way, like a ring buffer of the latest
+        /// items, whether or not it is [`bounded`]. The removed item is
+        /// returned. Without any capacity, `data` itself is returned.
+        ///
+        /// [`bounded`]: #method.bounded
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::bounded(2);
+        /// assert_eq!(queue.enqueue_overwrite(1), None);
+        /// assert_eq!(queue.enqueue_overwrite(2), None);
+        /// assert_eq!(queue.enqueue_overwrite(3), Some(1));
+        /// assert_eq!(queue.dequeue(), Some(2));
+        /// assert_eq!(queue.dequeue(), Some(3));
+        /// ```
+        pub fn enqueue_overwrite(&mut self, data: T) -> Option<T> {
+            if self.cap == 0 {
+                return Some(data);
+            }
+            let oldest = if self.has_space() {
+                None
+            } else {
+                self.dequeue()
+            };
+            // there is space now
+            let _ = self.enqueue(data);
+            oldest
+        }
+
+        /// Adds all items to the queue (FIFO), returning the ones which did not fit
+        ///
+        /// A queue which is not [`bounded`] grows whenever it is full, so
+        /// every item fits and the returned vector is empty.
+        ///
+        /// [`bounded`]: #method.bounded
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// let overflow = queue.enqueue_all(vec![1, 2, 3]);
+        /// assert!(overflow.is_empty());
+        /// assert_eq!(queue.len(), 3);
+        ///
+        /// let mut queue = data_structures::queues::queue::Queue::bounded(2);
+        /// let overflow = queue.enqueue_all(vec![1, 2, 3]);
+        /// assert_eq!(overflow, vec![3]);
+        /// ```
+        pub fn enqueue_all(&mut self, items: impl IntoIterator<Item = T>) -> Vec<T> {
+            items
+                .into_iter()
+                .filter_map(|data| self.enqueue(data).err())
+                .collect()
+        }
+
+        /// Gets the item with sequence number `seq`
+        ///
+        /// Returns `None` if the item is no longer in the queue. The queue is
+        /// scanned from the front to find the item.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// let seq = queue.enqueue_tracked(1).unwrap();
+        /// assert_eq!(queue.get_by_seq(seq), Some(&1));
+        /// queue.dequeue();
+        /// assert_eq!(queue.get_by_seq(seq), None);
+        /// ```
+        pub fn get_by_seq(&self, seq: usize) -> Option<&T> {
+            (0..self.len())
+                .map(|i| self.slot(i))
+                .find(|(item_seq, _)| *item_seq == seq)
+                .map(|(_, data)| data)
+        }
+
+        /// Removes an item from the queue (FIFO)
+        ///
+        /// Returns `None` if the queue is empty
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(2).unwrap();
+        /// queue.enqueue(3).unwrap();
+        /// assert_eq!(queue.dequeue(), Some(1));
+        /// assert_eq!(queue.dequeue(), Some(2));
+        /// assert_eq!(queue.dequeue(), Some(3));
+        /// assert_eq!(queue.dequeue(), None);
+        /// ```
+        pub fn dequeue(&mut self) -> Option<T> {
+            self.dequeue_with_age().map(|(data, _)| data)
+        }
+
+        /// Removes an item from the queue (FIFO), together with its age
+        ///
+        /// The age is the number of items enqueued after this one, i.e. the
+        /// number of enqueues that happened while it was waiting in the queue.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(2).unwrap();
+        /// queue.enqueue(3).unwrap();
+        /// assert_eq!(queue.dequeue_with_age(), Some((1, 2)));
+        /// queue.enqueue(4).unwrap();
+        /// assert_eq!(queue.dequeue_with_age(), Some((2, 2)));
+        /// ```
+        pub fn dequeue_with_age(&mut self) -> Option<(T, usize)> {
+            if self.empty() {
+                None
+            } else {
+                let (seq, data) = self.take_at(self.head);
+                self.incr_head();
+                self.count -= 1;
+                Some((data, self.seq - seq - 1))
+            }
+        }
+
+        /// Removes the most recently added item from the queue (LIFO)
+        ///
+        /// Returns `None` if the queue is empty
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(2).unwrap();
+        /// assert_eq!(queue.dequeue_back(), Some(2));
+        /// assert_eq!(queue.dequeue_back(), Some(1));
+        /// assert_eq!(queue.dequeue_back(), None);
+        /// ```
+        pub fn dequeue_back(&mut self) -> Option<T> {
+            if self.empty() {
+                None
+            } else {
+                self.decr_tail();
+                let (_, data) = self.take_at(self.tail);
+                self.count -= 1;
+                Some(data)
+            }
+        }
+
+        /// The item at the front of the queue, which is dequeued next
+        ///
+        /// Returns `None` if the queue is empty
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// assert_eq!(queue.peek(), None);
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(2).unwrap();
+        /// assert_eq!(queue.peek(), Some(&1));
+        /// ```
+        pub fn peek(&self) -> Option<&T> {
+            if self.empty() {
+                None
+            } else {
+                Some(&self.slot(0).1)
+            }
+        }
+
+        /// The item at the back of the queue, which was enqueued last
+        ///
+        /// Returns `None` if the queue is empty
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// assert_eq!(queue.peek_back(), None);
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(2).unwrap();
+        /// assert_eq!(queue.peek_back(), Some(&2));
+        /// ```
+        pub fn peek_back(&self) -> Option<&T> {
+            if self.empty() {
+                None
+            } else {
+                Some(&self.slot(self.len() - 1).1)
+            }
+        }
+
+        /// Removes the oldest items until at most `max_len` items are left
+        ///
+        /// The removed items are returned from oldest to newest, and the
+        /// `max_len` most recent items stay in the queue.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue_all(vec![1, 2, 3]);
+        /// assert_eq!(queue.cap_to(1), vec![1, 2]);
+        /// assert_eq!(queue.dequeue(), Some(3));
+        /// ```
+        pub fn cap_to(&mut self, max_len: usize) -> Vec<T> {
+            let excess = self.len().saturating_sub(max_len);
+            (0..excess).filter_map(|_| self.dequeue()).collect()
+        }
+
+        /// Exchanges the items (and capacities) of two queues
+        ///
+        /// Only the internal fields are swapped, so no items are moved. The
+        /// sequence numbers move along with the items.
+        ///
+        /// ```
+        /// let mut filling = data_structures::queues::queue::Queue::new();
+        /// let mut draining = data_structures::queues::queue::Queue::new();
+        /// filling.enqueue(1).unwrap();
+        /// filling.swap_contents(&mut draining);
+        /// assert!(filling.empty());
+        /// assert_eq!(draining.dequeue(), Some(1));
+        /// ```
+        pub fn swap_contents(&mut self, other: &mut Queue<T>) {
+            std::mem::swap(self, other);
+        }
+
+        /// Removes all items from the queue, keeping its capacity
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(1).unwrap();
+        /// queue.clear();
+        /// assert!(queue.empty());
+        /// assert_eq!(queue.dequeue(), None);
+        /// ```
+        pub fn clear(&mut self) {
+            // slots outside the queue are already empty, so this only drops
+            // the items in the queue
+            self.list.iter_mut().for_each(|slot| *slot = None);
+            self.head = 0;
+            self.tail = 0;
+            self.count = 0;
+        }
+
+        /// Checks if there are items in the queue
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// assert!(queue.empty());
+        /// queue.enqueue(1).unwrap();
+        /// assert!(!queue.empty());
+        /// ```
+        pub fn empty(&self) -> bool {
+            self.count == 0
+        }
+
+        /// The number of items in the queue
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(1).unwrap();
+        /// assert_eq!(queue.len(), 3);
+        /// ```
+        #[allow(clippy::len_without_is_empty)]
+        pub fn len(&self) -> usize {
+            self.count
+        }
+
+        /// The number of items the queue can hold without growing
+        ///
+        /// ```
+        /// let queue: data_structures::queues::queue::Queue<i32> =
+        ///     data_structures::queues::queue::Queue::with_capacity(4);
+        /// assert_eq!(queue.capacity(), 4);
+        /// ```
+        pub fn capacity(&self) -> usize {
+            self.cap
+        }
+
+        /// Grows the queue such that it can hold at least `additional` more items
+        ///
+        /// Like a resize, this moves the items to the start of a new internal
+        /// list. Nothing happens when there is enough room already.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::with_capacity(2);
+        /// queue.enqueue(1).unwrap();
+        /// queue.reserve(5);
+        /// assert!(queue.capacity() >= 6);
+        /// assert_eq!(queue.dequeue(), Some(1));
+        /// ```
+        pub fn reserve(&mut self, additional: usize) {
+            let needed = self.len().saturating_add(additional);
+            if needed > self.cap {
+                self.relocate(needed);
+            }
+        }
+
+        /// Shrinks the capacity of the queue to the number of items in it
+        ///
+        /// Like [`reserve`], this moves the items to the start of a new
+        /// internal list. Nothing happens when the queue is full already.
+        ///
+        /// [`reserve`]: #method.reserve
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::with_capacity(8);
+        /// queue.enqueue(1).unwrap();
+        /// queue.shrink_to_fit();
+        /// assert_eq!(queue.capacity(), 1);
+        /// assert_eq!(queue.dequeue(), Some(1));
+        /// ```
+        pub fn shrink_to_fit(&mut self) {
+            if self.len() < self.cap {
+                self.relocate(self.len());
+            }
+        }
+
+        /// The fraction of the queue's capacity which is in use
+        ///
+        /// Returns `0.0` for an empty queue (or one without any capacity).
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::with_capacity(4);
+        /// assert_eq!(queue.load_factor(), 0.0);
+        /// queue.enqueue(1).unwrap();
+        /// assert_eq!(queue.load_factor(), 0.25);
+        /// ```
+        pub fn load_factor(&self) -> f64 {
+            let capacity = self.capacity();
+            if capacity == 0 {
+                0.0
+            } else {
+                self.len() as f64 / capacity as f64
+            }
+        }
+
+        /// Clone up to `len` items, starting at position `start` in the queue
+        ///
+        /// The items are not removed from the queue. Positions are counted
+        /// from the front of the queue, and the range is clamped to the items
+        /// in the queue.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(2).unwrap();
+        /// queue.enqueue(3).unwrap();
+        /// assert_eq!(queue.peek_range(1, 5), vec![2, 3]);
+        /// assert_eq!(queue.len(), 3);
+        /// ```
+        pub fn peek_range(&self, start: usize, len: usize) -> Vec<T>
+        where
+            T: Clone,
+        {
+            let end = self.len().min(start.saturating_add(len));
+            (start..end).map(|i| self.slot(i).1.clone()).collect()
+        }
+
+        /// Iterate over the items from front to back, without removing them
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(2).unwrap();
+        /// let items: Vec<&i32> = queue.iter().collect();
+        /// assert_eq!(items, vec![&1, &2]);
+        /// ```
+        pub fn iter(&self) -> impl Iterator<Item = &T> {
+            self.iter_from(0)
+        }
+
+        /// Iterate over the items from position `start` to the back of the queue
+        ///
+        /// The iterator is empty when `start` is not smaller than the length.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(2).unwrap();
+        /// queue.enqueue(3).unwrap();
+        /// let rest: Vec<&i32> = queue.iter_from(1).collect();
+        /// assert_eq!(rest, vec![&2, &3]);
+        /// ```
+        pub fn iter_from(&self, start: usize) -> impl Iterator<Item = &T> {
+            let len = self.len();
+            (start.min(len)..len).map(move |i| &self.slot(i).1)
+        }
+
+        /// References to all items in the queue, from front to back
+        ///
+        /// The items are neither cloned nor removed from the queue.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(2).unwrap();
+        /// assert_eq!(queue.refs(), vec![&1, &2]);
+        /// assert_eq!(queue.len(), 2);
+        /// ```
+        pub fn refs(&self) -> Vec<&T> {
+            self.iter().collect()
+        }
+
+        /// Returns the first `Some` value of `f` on the items, from the front
+        ///
+        /// The items are not removed from the queue.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(4).unwrap();
+        /// queue.enqueue(9).unwrap();
+        /// let big = queue.find_map(|x| if *x > 3 { Some(x * 10) } else { None });
+        /// assert_eq!(big, Some(40));
+        /// ```
+        pub fn find_map<B, F>(&self, f: F) -> Option<B>
+        where
+            F: FnMut(&T) -> Option<B>,
+        {
+            self.iter_from(0).find_map(f)
+        }
+
+        /// Sorts the items in the queue in ascending order
+        ///
+        /// After sorting, the smallest item is at the front of the queue.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(3).unwrap();
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(2).unwrap();
+        /// queue.sort();
+        /// assert_eq!(queue.dequeue(), Some(1));
+        /// assert_eq!(queue.dequeue(), Some(2));
+        /// assert_eq!(queue.dequeue(), Some(3));
+        /// ```
+        pub fn sort(&mut self)
+        where
+            T: Ord,
+        {
+            self.sort_by(T::cmp)
+        }
+
+        /// Sorts the items in the queue with a comparator function
+        ///
+        /// The sort is stable, so equal items keep their order in the queue.
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(3).unwrap();
+        /// queue.enqueue(2).unwrap();
+        /// queue.sort_by(|a, b| b.cmp(a));
+        /// assert_eq!(queue.dequeue(), Some(3));
+        /// assert_eq!(queue.dequeue(), Some(2));
+        /// assert_eq!(queue.dequeue(), Some(1));
+        /// ```
+        pub fn sort_by<F>(&mut self, mut compare: F)
+        where
+            F: FnMut(&T, &T) -> std::cmp::Ordering,
+        {
+            // rotate the items to the start of the internal list in place,
+            // so the first len slots hold the items
+            let len = self.len();
+            self.list.rotate_left(self.head);
+            self.head = 0;
+            // a full list wraps the tail around to the start
+            self.tail = if len == self.cap { 0 } else { len };
+            self.list[..len]
+                .sort_by(|a, b| compare(&a.as_ref().unwrap().1, &b.as_ref().unwrap().1));
+        }
+
+        /// Consumes the queue, returning its items sorted in ascending order
+        ///
+        /// ```
+        /// let mut queue = data_structures::queues::queue::Queue::new();
+        /// queue.enqueue(3).unwrap();
+        /// queue.enqueue(1).unwrap();
+        /// queue.enqueue(2).unwrap();
+        /// assert_eq!(queue.into_sorted_vec(), vec![1, 2, 3]);
+        /// ```
+        pub fn into_sorted_vec(mut self) -> Vec<T>
+        where
+            T: Ord,
+        {
+            // after sorting, the internal list only holds the items in the queue
+            self.sort();
+            self.list
+                .into_iter()
+                .flatten()
+                .map(|(_, data)| data)
+                .collect()
+        }
+
+        // private helper functions
+
+        /// Index in the internal list of the item at position `i` in the queue
+        fn index(&self, i: usize) -> usize {
+            (self.head + i) % self.cap
+        }
+
+        /// The item and its sequence number at position `i` in the queue
+        fn slot(&self, i: usize) -> &(usize, T) {
+            self.list[self.index(i)]
+                .as_ref()
+                .expect("positions in the queue hold an item")
+        }
+
+        /// Grow the queue if it is full, returning false if it is bounded instead
+        fn make_space(&mut self) -> bool {
+            if !self.has_space() {
+                if self.bounded {
+                    return false;
+                }
+                self.resize();
+            }
+            true
+        }
+
+        fn has_space(&self) -> bool {
+            self.count < self.cap
+        }
+
+        fn incr_head(&mut self) {
+            self.head = (self.head + 1) % self.cap;
+        }
+
+        fn decr_head(&mut self) {
+            self.head = (self.head + self.cap - 1) % self.cap;
+        }
+
+        fn incr_tail(&mut self) {
+            self.tail = (self.tail + 1) % self.cap;
+        }
+
+        fn decr_tail(&mut self) {
+            self.tail = (self.tail + self.cap - 1) % self.cap;
+        }
+
+        /// Move the item and its sequence number out of the internal list at `index`
+        fn take_at(&mut self, index: usize) -> (usize, T) {
+            self.list[index]
+                .take()
+                .expect("positions in the queue hold an item")
+        }
+
+        /// Double the capacity of the interal list
+        fn resize(&mut self) {
+            self.relocate((self.cap * 2).max(1));
+        }
+
+        /// Move all items into a new internal list with the given capacity
+        ///
+        /// Creates a new vector with the capacity and moves all items from the
+        /// old list into it, such that the queue starts at index 0.
+        fn relocate(&mut self, capacity: usize) {
+            let len = self.len();
+            let mut new_list = empty_slots(capacity);
+            // move items into this vector
+            for (i, slot) in new_list.iter_mut().enumerate().take(len) {
+                let index = self.index(i);
+                *slot = self.list[index].take();
+            }
+            self.list = new_list;
+            self.cap = capacity;
+            self.head = 0;
+            // a full list wraps the tail around to the start
+            self.tail = if len == capacity { 0 } else { len };
+        }
+    }
+
+    // A list of `capacity` slots without an item
+    fn empty_slots<T>(capacity: usize) -> Vec<Option<T>> {
+        std::iter::repeat_with(|| None).take(capacity).collect()
+    }
+
+    /// Owning iterator over a [`Queue`], which dequeues the items (FIFO)
+    pub struct IntoIter<T>(Queue<T>);
+
+    impl<T> Iterator for IntoIter<T> {
+        type Item = T;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            self.0.dequeue()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.0.len(), Some(self.0.len()))
+        }
+    }
+
+    impl<T> IntoIterator for Queue<T> {
+        type Item = T;
+        type IntoIter = IntoIter<T>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            IntoIter(self)
+        }
+    }
+
+    impl<T> FromIterator<T> for Queue<T> {
+        /// Enqueue all items, with an initial capacity from the size hint
+        fn from_iter<I>(iter: I) -> Self
+        where
+            I: IntoIterator<Item = T>,
+        {
+            let iter = iter.into_iter();
+            let mut queue = Queue::with_capacity(iter.size_hint().0);
+            for data in iter {
+                // an unbounded queue always has space
+                let _ = queue.enqueue(data);
+            }
+            queue
+        }
+    }
+
+    impl<T> Extend<T> for Queue<T> {
+        /// Enqueue all items, stopping when a bounded queue is full
+        ///
+        /// Once a bounded queue is full, the rest of the items are silently
+        /// dropped, use [`Queue::enqueue_all`] to get them back instead. Any
+        /// other queue grows at least by doubling, like a resize, so many
+        /// small extends stay cheap.
+        fn extend<I>(&mut self, iter: I)
+        where
+            I: IntoIterator<Item = T>,
+        {
+            let iter = iter.into_iter();
+            let needed = self.len().saturating_add(iter.size_hint().0);
+            if !self.bounded && needed > self.cap {
+                self.relocate(needed.max(self.cap * 2));
+            }
+            for data in iter {
+                if self.enqueue(data).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    impl<T> Default for Queue<T> {
+        fn default() -> Self {
+            Queue::new()
+        }
+    }
+
+    impl<T> Clone for Queue<T>
+    where
+        T: Clone,
+    {
+        /// Clone only the items in the queue, which start at index 0 in the clone
+        fn clone(&self) -> Self {
+            let len = self.len();
+            let mut list = empty_slots(self.cap);
+            for (i, slot) in list.iter_mut().enumerate().take(len) {
+                *slot = Some(self.slot(i).clone());
+            }
+            Queue {
+                list,
+                head: 0,
+                // a full list wraps the tail around to the start
+                tail: if len == self.cap { 0 } else { len },
+                ..*self
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<T> serde::Serialize for Queue<T>
+    where
+        T: serde::Serialize,
+    {
+        /// Serializes the items as a sequence, from front to back
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.collect_seq(self.iter())
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de, T> serde::Deserialize<'de> for Queue<T>
+    where
+        T: serde::Deserialize<'de>,
+    {
+        /// Deserializes a sequence of items, enqueueing them from front to back
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            Vec::deserialize(deserializer).map(Queue::from_iter)
+        }
+    }
+
+    impl<T> std::fmt::Debug for Queue<T>
+    where
+        T: std::fmt::Debug,
+    {
+        /// Only shows the items in the queue, from front to back
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_list().entries(self.iter()).finish()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn init_test() {
+            let q: Queue<i32> = Queue::new();
+            assert_eq!(q.cap, DEFAULT_INIT_QUEUE_CAPACITY);
+            assert_eq!(q.head, 0);
+            assert_eq!(q.tail, 0);
+        }
+
+        #[test]
+        fn default_test() {
+            let mut q: Queue<i32> = Default::default();
+            assert!(q.empty());
+            assert_eq!(q.cap, DEFAULT_INIT_QUEUE_CAPACITY);
+            assert_eq!(q.dequeue(), None);
+        }
+
+        #[test]
+        fn enqueue_test() {
+            let mut q = Queue::new();
+            q.enqueue(1).unwrap();
+            assert_eq!(q.head, 0);
+            assert_eq!(q.tail, 1);
+            assert_eq!(q.list.first(), Some(&Some((0, 1))));
+        }
+
+        #[test]
+        fn dequeue_test() {
+            let mut q = Queue::new();
+            q.enqueue(1).unwrap();
+            assert_eq!(q.dequeue(), Some(1));
+            assert_eq!(q.dequeue(), None);
+            assert_eq!(q.head, 1);
+            assert_eq!(q.tail, 1);
+        }
+
+        #[test]
+        fn fifo_test() {
+            let mut q = Queue::new();
+            q.enqueue(1).unwrap();
+            q.enqueue(2).unwrap();
+            q.enqueue(3).unwrap();
+            q.enqueue(4).unwrap();
+            assert_eq!(q.dequeue(), Some(1));
+            assert_eq!(q.dequeue(), Some(2));
+            assert_eq!(q.dequeue(), Some(3));
+            assert_eq!(q.dequeue(), Some(4));
+            assert_eq!(q.dequeue(), None);
+        }
+
+        #[test]
+        fn dequeue_with_age_test() {
+            let mut q = Queue::new();
+            assert_eq!(q.dequeue_with_age(), None);
+
+            q.enqueue(1).unwrap();
+            assert_eq!(q.dequeue_with_age(), Some((1, 0)));
+
+            q.enqueue(2).unwrap();
+            q.enqueue(3).unwrap();
+            q.enqueue(4).unwrap();
+            assert_eq!(q.dequeue_with_age(), Some((2, 2)));
+            q.enqueue(5).unwrap();
+            assert_eq!(q.dequeue_with_age(), Some((3, 2)));
+            assert_eq!(q.dequeue_with_age(), Some((4, 1)));
+            assert_eq!(q.dequeue_with_age(), Some((5, 0)));
+            assert_eq!(q.dequeue_with_age(), None);
+        }
+
+        #[test]
+        fn owned_items_test() {
+            // wrap around and grow with items which own heap memory
+            let mut q = Queue::with_capacity(3);
+            for round in 0..5 {
+                q.enqueue(format!("a{round}")).unwrap();
+                q.enqueue(format!("b{round}")).unwrap();
+                assert_eq!(q.dequeue(), Some(format!("a{round}")));
+                assert_eq!(q.dequeue(), Some(format!("b{round}")));
+            }
+            for i in 0..5 {
+                q.enqueue(i.to_string()).unwrap();
+            }
+            assert_eq!(q.dequeue_back(), Some(String::from("4")));
+            assert_eq!(q.dequeue(), Some(String::from("0")));
+            assert_eq!(q.len(), 3);
+
+            let mut boxes = Queue::new();
+            boxes.enqueue(Box::new(1)).unwrap();
+            boxes.enqueue(Box::new(2)).unwrap();
+            assert_eq!(boxes.dequeue(), Some(Box::new(1)));
+            boxes.sort_by(|a, b| b.cmp(a));
+            assert_eq!(boxes.into_sorted_vec(), vec![Box::new(2)]);
+        }
+
+        #[test]
+        fn dequeue_back_test() {
+            let mut q = Queue::new();
+            q.enqueue(1).unwrap();
+            q.enqueue(2).unwrap();
+            q.enqueue(3).unwrap();
+            assert_eq!(q.dequeue_back(), Some(3));
+            assert_eq!(q.dequeue_back(), Some(2));
+            assert_eq!(q.dequeue_back(), Some(1));
+            assert_eq!(q.dequeue_back(), None);
+
+            // wrap the tail around to index 0
+            let mut q = Queue::with_capacity(3);
+            q.enqueue(1).unwrap();
+            q.enqueue(2).unwrap();
+            q.dequeue();
+            q.enqueue(3).unwrap();
+            assert_eq!(q.tail, 0);
+            assert_eq!(q.dequeue_back(), Some(3));
+            assert_eq!(q.tail, 2);
+            q.enqueue(4).unwrap();
+            assert_eq!(q.dequeue(), Some(2));
+            assert_eq!(q.dequeue(), Some(4));
+            assert_eq!(q.dequeue(), None);
+        }
+
+        #[test]
+        fn peek_test() {
+            let mut q = Queue::with_capacity(3);
+            assert_eq!(q.peek(), None);
+            assert_eq!(q.peek_back(), None);
+
+            q.enqueue(1).unwrap();
+            assert_eq!(q.peek(), Some(&1));
+            assert_eq!(q.peek_back(), Some(&1));
+
+            // wrap the tail around to index 0, so the back is at the end
+            q.enqueue(2).unwrap();
+            q.dequeue();
+            q.enqueue(3).unwrap();
+            assert_eq!(q.tail, 0);
+            assert_eq!(q.peek(), Some(&2));
+            assert_eq!(q.peek_back(), Some(&3));
+
+            q.dequeue();
+            q.enqueue(4).unwrap();
+            assert!(q.tail < q.head);
+            assert_eq!(q.peek(), Some(&3));
+            assert_eq!(q.peek_back(), Some(&4));
+            assert_eq!(q.len(), 2);
+        }
+
+        #[test]
+        fn cap_to_test() {
+            let mut q = Queue::new();
+            q.enqueue_all(1..=5);
+            assert_eq!(q.cap_to(3), vec![1, 2]);
+            assert_eq!(q.len(), 3);
+            assert!(q.cap_to(3).is_empty());
+            assert!(q.cap_to(10).is_empty());
+            assert_eq!(q.refs(), vec![&3, &4, &5]);
+
+            assert_eq!(q.cap_to(0), vec![3, 4, 5]);
+            assert!(q.empty());
+        }
+
+        #[test]
+        fn enqueue_front_test() {
+            let mut q = Queue::new();
+            q.enqueue_front(2).unwrap();
+            q.enqueue(3).unwrap();
+            q.enqueue_front(1).unwrap();
+            assert_eq!(q.head, DEFAULT_INIT_QUEUE_CAPACITY - 2);
+            assert_eq!(q.refs(), vec![&1, &2, &3]);
+            assert_eq!(q.dequeue_back(), Some(3));
+            assert_eq!(q.dequeue(), Some(1));
+            q.enqueue_front(0).unwrap();
+            assert_eq!(q.dequeue_back(), Some(2));
+            assert_eq!(q.dequeue_back(), Some(0));
+            assert_eq!(q.dequeue(), None);
+        }
+
+        #[test]
+        fn enqueue_front_resize_test() {
+            // the items straddle the end of the internal list when it grows
+            let mut q = Queue::with_capacity(4);
+            q.enqueue(2).unwrap();
+            q.enqueue(3).unwrap();
+            q.enqueue_front(1).unwrap();
+            q.enqueue_front(0).unwrap();
+            assert_eq!(q.cap, 4);
+            assert_eq!(q.head, 2);
+            q.enqueue(4).unwrap(); // resize here
+            assert_eq!(q.cap, 8);
+            assert_eq!(q.len(), 5);
+            assert_eq!(q.refs(), vec![&0, &1, &2, &3, &4]);
+            assert_eq!(q.dequeue(), Some(0));
+            assert_eq!(q.dequeue_back(), Some(4));
+            assert_eq!(q.dequeue(), Some(1));
+        }
 
-        fn has_space(&self) -> bool {
-            self.head != (self.tail + 1) % self.list.capacity()
+        #[test]
+        fn enqueue_all_test() {
+            let mut q = Queue::with_capacity(3);
+            q.enqueue(1).unwrap();
+            q.enqueue(2).unwrap();
+            let overflow = q.enqueue_all(vec![3, 4, 5]); // resize here
+            assert!(overflow.is_empty());
+            for i in 1..=5 {
+                assert_eq!(q.dequeue(), Some(i));
+            }
+            assert_eq!(q.dequeue(), None);
+
+            let mut q = Queue::bounded(3);
+            q.enqueue(1).unwrap();
+            q.enqueue(2).unwrap();
+            let overflow = q.enqueue_all(vec![3, 4, 5]);
+            assert_eq!(overflow, vec![4, 5]);
+            assert_eq!(q.capacity(), 3);
+            assert_eq!(q.into_iter().collect::<Vec<i32>>(), vec![1, 2, 3]);
         }
 
-        fn incr_head(&mut self) {
-            self.head = (self.head + 1) % self.list.capacity();
+        #[test]
+        fn enqueue_result_test() {
+            let mut q = Queue::bounded(2);
+            assert_eq!(q.enqueue(1), Ok(()));
+            assert_eq!(q.enqueue(2), Ok(()));
+            assert_eq!(q.enqueue(3), Err(3));
+            assert_eq!(q.capacity(), 2);
+            assert_eq!(q.dequeue(), Some(1));
+            assert_eq!(q.enqueue(4), Ok(()));
+            assert_eq!(q.refs(), vec![&2, &4]);
+
+            // a queue which is not bounded grows instead
+            let mut q = Queue::with_capacity(1);
+            assert_eq!(q.enqueue(1), Ok(()));
+            assert_eq!(q.enqueue(2), Ok(()));
+            assert_eq!(q.capacity(), 2);
+
+            let mut q = Queue::bounded(0);
+            assert_eq!(q.enqueue(1), Err(1));
         }
 
-        fn incr_tail(&mut self) {
-            self.tail = (self.tail + 1) % self.list.capacity();
+        #[test]
+        fn bounded_enqueue_full_test() {
+            let mut q = Queue::bounded(2);
+            assert_eq!(q.enqueue(1), Ok(()));
+            assert_eq!(q.enqueue(2), Ok(()));
+            assert_eq!(q.enqueue(3), Err(3));
+            assert_eq!(q.enqueue_front(0), Err(0));
+            assert_eq!(q.enqueue_tracked(4), Err(4));
+            assert_eq!(q.capacity(), 2);
+            assert_eq!(q.refs(), vec![&1, &2]);
+
+            // extend stops at the capacity
+            let mut q = Queue::bounded(3);
+            q.enqueue(1).unwrap();
+            q.extend(vec![2, 3, 4, 5]);
+            assert_eq!(q.capacity(), 3);
+            assert_eq!(q.refs(), vec![&1, &2, &3]);
+            q.extend(vec![6]);
+            assert_eq!(q.len(), 3);
+
+            let mut q = Queue::bounded(0);
+            assert_eq!(q.enqueue(1), Err(1));
+            q.extend(vec![1, 2]);
+            assert!(q.empty());
         }
 
-        /// Double the capacity of the interal list
-        ///
-        /// Creates a new vector with double the capacity and moves all items
-        /// from the old list into it.
-        fn resize(&mut self) {
-            // make new vector with twice the capacity
-            let mut new_list = Vec::with_capacity(self.list.capacity() * 2);
-            // move items into this vector
-            if self.head <= self.tail {
-                for i in self.list.drain(self.head..self.tail) {
-                    new_list.insert(new_list.len(), i);
-                }
-            } else {
-                for i in self.list.drain(self.head..) {
-                    new_list.insert(new_list.len(), i);
-                }
-                for i in self.list.drain(..self.tail) {
-                    new_list.insert(new_list.len(), i);
-                }
+        #[test]
+        fn enqueue_overwrite_test() {
+            let mut q = Queue::bounded(3);
+            for i in 0..3 {
+                assert_eq!(q.enqueue_overwrite(i), None);
             }
-            self.list = new_list;
-            self.head = 0;
-            self.tail = self.list.len();
-        }
-    }
+            assert_eq!(q.enqueue_overwrite(3), Some(0));
+            assert_eq!(q.enqueue_overwrite(4), Some(1));
+            assert_eq!(q.capacity(), 3);
+            assert_eq!(q.refs(), vec![&2, &3, &4]);
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
+            let mut q = Queue::with_capacity(1);
+            assert_eq!(q.enqueue_overwrite(String::from("a")), None);
+            assert_eq!(
+                q.enqueue_overwrite(String::from("b")),
+                Some(String::from("a"))
+            );
+            assert_eq!(q.capacity(), 1);
+
+            let mut q = Queue::bounded(0);
+            assert_eq!(q.enqueue_overwrite(1), Some(1));
+            assert!(q.empty());
+        }
 
         #[test]
-        fn init_test() {
-            let q: Queue<i32> = Queue::new();
-            assert_eq!(q.list.capacity(), DEFAULT_INIT_QUEUE_CAPACITY);
-            assert_eq!(q.head, 0);
-            assert_eq!(q.tail, 0);
+        fn enqueue_tracked_test() {
+            let mut q = Queue::with_capacity(3);
+            let first = q.enqueue_tracked(1).unwrap();
+            let second = q.enqueue_tracked(2).unwrap();
+            assert!(first < second);
+            assert_eq!(q.dequeue(), Some(1));
+            let third = q.enqueue_tracked(3).unwrap(); // wraps the tail around
+            let fourth = q.enqueue_tracked(4).unwrap(); // resize here
+            assert!(third < fourth);
+
+            assert_eq!(q.get_by_seq(first), None);
+            assert_eq!(q.get_by_seq(second), Some(&2));
+            assert_eq!(q.get_by_seq(third), Some(&3));
+            assert_eq!(q.get_by_seq(fourth), Some(&4));
+            assert_eq!(q.get_by_seq(fourth + 1), None);
+
+            q.dequeue_back();
+            assert_eq!(q.get_by_seq(fourth), None);
+            let fifth = q.enqueue_tracked(5).unwrap();
+            assert_ne!(fifth, fourth);
+            assert_eq!(q.get_by_seq(fifth), Some(&5));
         }
 
         #[test]
-        fn enqueue_test() {
-            let mut q = Queue::new();
-            q.enqueue(1);
-            assert_eq!(q.head, 0);
-            assert_eq!(q.tail, 1);
-            assert_eq!(q.list.get(0), Some(&1));
+        fn from_iter_test() {
+            let q: Queue<i32> = (0..10).collect();
+            assert_eq!(q.capacity(), 10);
+            assert_eq!(
+                q.into_iter().collect::<Vec<i32>>(),
+                (0..10).collect::<Vec<i32>>()
+            );
+
+            // without a size hint, the queue grows while collecting
+            let mut q: Queue<i32> = (0..10).filter(|i| i % 3 != 0).collect();
+            for i in [1, 2, 4, 5, 7, 8] {
+                assert_eq!(q.dequeue(), Some(i));
+            }
+            assert_eq!(q.dequeue(), None);
         }
 
         #[test]
-        fn dequeue_test() {
-            let mut q = Queue::new();
-            q.enqueue(1);
-            assert_eq!(q.dequeue(), Some(1));
+        fn extend_test() {
+            let mut q = Queue::with_capacity(4);
+            q.extend(0..3);
+            q.dequeue();
+            q.dequeue();
+            q.extend(vec![3, 4]);
+            assert!(q.tail < q.head);
+
+            q.extend((5..9).filter(|_| true)); // resize here
+            assert_eq!(q.len(), 7);
+            for i in 2..9 {
+                assert_eq!(q.dequeue(), Some(i));
+            }
             assert_eq!(q.dequeue(), None);
-            assert_eq!(q.head, 1);
-            assert_eq!(q.tail, 1);
+
+            // small extends double the capacity instead of growing by one
+            let mut q = Queue::with_capacity(1);
+            for i in 0..9 {
+                q.extend(std::iter::once(i));
+            }
+            assert_eq!(q.capacity(), 16);
+            assert_eq!(q.len(), 9);
+            q.extend(0..20);
+            assert_eq!(q.capacity(), 32);
+            q.extend(0..100);
+            assert_eq!(q.capacity(), 129);
+
+            let mut q = Queue::bounded(3);
+            q.extend(vec![1, 2]);
+            q.extend(vec![3]);
+            assert_eq!(q.capacity(), 3);
         }
 
         #[test]
-        fn fifo_test() {
-            let mut q = Queue::new();
-            q.enqueue(1);
-            q.enqueue(2);
-            q.enqueue(3);
-            q.enqueue(4);
+        fn try_from_iter_test() {
+            let items: Vec<Result<i32, &str>> = vec![Ok(1), Ok(2), Ok(3)];
+            let mut q = Queue::try_from_iter(items).unwrap();
             assert_eq!(q.dequeue(), Some(1));
             assert_eq!(q.dequeue(), Some(2));
             assert_eq!(q.dequeue(), Some(3));
-            assert_eq!(q.dequeue(), Some(4));
             assert_eq!(q.dequeue(), None);
+
+            let mut consumed = 0;
+            let items = vec![Ok(1), Err("second"), Ok(3), Err("fourth")]
+                .into_iter()
+                .inspect(|_| consumed += 1);
+            assert_eq!(Queue::try_from_iter(items).err(), Some("second"));
+            assert_eq!(consumed, 2);
+        }
+
+        #[test]
+        fn swap_contents_test() {
+            let mut a = Queue::with_capacity(3);
+            a.enqueue(1).unwrap();
+            a.enqueue(2).unwrap();
+            a.dequeue();
+            a.enqueue(3).unwrap(); // wraps the tail around
+            let mut b = Queue::with_capacity(8);
+            b.enqueue(10).unwrap();
+
+            a.swap_contents(&mut b);
+            assert_eq!(a.cap, 8);
+            assert_eq!(b.cap, 3);
+            assert_eq!(a.len(), 1);
+            assert_eq!(b.len(), 2);
+
+            a.enqueue(11).unwrap();
+            b.enqueue(4).unwrap(); // resize here
+            assert_eq!(a.dequeue(), Some(10));
+            assert_eq!(a.dequeue(), Some(11));
+            assert_eq!(a.dequeue(), None);
+            assert_eq!(b.dequeue(), Some(2));
+            assert_eq!(b.dequeue(), Some(3));
+            assert_eq!(b.dequeue(), Some(4));
+            assert_eq!(b.dequeue(), None);
         }
 
         #[test]
         fn wrapping_index_test() {
             let mut q = Queue::with_capacity(3);
-            q.enqueue(1); // tail = 1
+            q.enqueue(1).unwrap(); // tail = 1
             assert_eq!(q.dequeue(), Some(1));
 
-            q.enqueue(2); // tail = 2
+            q.enqueue(2).unwrap(); // tail = 2
             assert_eq!(q.dequeue(), Some(2));
 
-            q.enqueue(3); // tail = 0
+            q.enqueue(3).unwrap(); // tail = 0
             assert_eq!(q.dequeue(), Some(3));
 
             assert_eq!(q.head, 0);
             assert_eq!(q.tail, 0);
 
-            q.enqueue(4); // tail = 1
+            q.enqueue(4).unwrap(); // tail = 1
             assert_eq!(q.dequeue(), Some(4));
         }
 
         #[test]
         fn resize_test() {
             let mut q: Queue<i32> = Queue::new();
-            assert_eq!(q.list.capacity(), DEFAULT_INIT_QUEUE_CAPACITY);
+            assert_eq!(q.cap, DEFAULT_INIT_QUEUE_CAPACITY);
             q.resize();
-            assert_eq!(q.list.capacity(), DEFAULT_INIT_QUEUE_CAPACITY * 2);
+            assert_eq!(q.cap, DEFAULT_INIT_QUEUE_CAPACITY * 2);
             assert_eq!(q.head, 0);
             assert_eq!(q.tail, 0);
         }
@@ -337,10 +2581,10 @@ pub mod queue {
         #[test]
         fn resize_with_items_test() {
             let mut q: Queue<i32> = Queue::new();
-            assert_eq!(q.list.capacity(), DEFAULT_INIT_QUEUE_CAPACITY);
-            q.enqueue(1);
+            assert_eq!(q.cap, DEFAULT_INIT_QUEUE_CAPACITY);
+            q.enqueue(1).unwrap();
             q.resize();
-            assert_eq!(q.list.capacity(), DEFAULT_INIT_QUEUE_CAPACITY * 2);
+            assert_eq!(q.cap, DEFAULT_INIT_QUEUE_CAPACITY * 2);
             assert_eq!(q.head, 0);
             assert_eq!(q.tail, 1);
         }
@@ -348,22 +2592,88 @@ pub mod queue {
         #[test]
         fn resize_trigger_test() {
             let mut q = Queue::with_capacity(3);
-            q.enqueue(1);
-            q.enqueue(2);
-            assert_eq!(q.list.capacity(), 3);
-            q.enqueue(3); // resize here
-            assert_eq!(q.list.capacity(), 6);
+            q.enqueue(1).unwrap();
+            q.enqueue(2).unwrap();
+            q.enqueue(3).unwrap();
+            assert_eq!(q.cap, 3);
+            q.enqueue(4).unwrap(); // resize here
+            assert_eq!(q.cap, 6);
+        }
+
+        #[test]
+        fn full_capacity_test() {
+            let mut q = Queue::with_capacity(4);
+            for i in 0..4 {
+                q.enqueue(i).unwrap();
+            }
+            assert_eq!(q.cap, 4);
+            assert_eq!(q.len(), 4);
+            assert_eq!(q.head, q.tail);
+            assert!(!q.empty());
+            assert_eq!(q.refs(), vec![&0, &1, &2, &3]);
+
+            // a full queue which wraps around
+            q.dequeue();
+            q.enqueue(4).unwrap();
+            assert_eq!(q.cap, 4);
+            assert_eq!(q.refs(), vec![&1, &2, &3, &4]);
+            assert_eq!(q.dequeue_back(), Some(4));
+            assert_eq!(q.len(), 3);
+        }
+
+        #[test]
+        fn zero_capacity_test() {
+            let mut q = Queue::with_capacity(0);
+            assert!(q.empty());
+            q.sort();
+            q.enqueue(1).unwrap();
+            q.enqueue(2).unwrap();
+            assert_eq!(q.cap, 2);
+            assert_eq!(q.dequeue(), Some(1));
+            assert_eq!(q.dequeue(), Some(2));
+        }
+
+        #[test]
+        fn len_after_resize_test() {
+            let mut q = Queue::with_capacity(4);
+            let mut live = 0;
+            for i in 0..3 {
+                q.enqueue(i).unwrap();
+                live += 1;
+            }
+            // grow the queue while it wraps around
+            q.dequeue();
+            q.dequeue();
+            live -= 2;
+            for i in 3..9 {
+                q.enqueue(i).unwrap();
+                live += 1;
+                assert_eq!(q.len(), live);
+            }
+            assert_eq!(q.cap, 8);
+
+            // wrap the grown queue around
+            for i in 9..30 {
+                q.dequeue();
+                q.enqueue(i).unwrap();
+                assert_eq!(q.len(), live);
+            }
+            assert!(q.tail < q.head);
+            assert_eq!(q.len(), 7);
+            assert_eq!(q.refs(), vec![&23, &24, &25, &26, &27, &28, &29]);
         }
 
         #[test]
         fn resize_lifo_test() {
             let mut q = Queue::with_capacity(3);
-            q.enqueue(1);
-            q.enqueue(2);
-            q.enqueue(3); // resize here
-            q.enqueue(4);
-            q.enqueue(5);
-            q.enqueue(6);
+            q.enqueue(1).unwrap();
+            q.enqueue(2).unwrap();
+            q.enqueue(3).unwrap();
+            assert_eq!(q.capacity(), 3);
+            q.enqueue(4).unwrap(); // resize here
+            assert_eq!(q.capacity(), 6);
+            q.enqueue(5).unwrap();
+            q.enqueue(6).unwrap();
             assert_eq!(q.dequeue(), Some(1));
             assert_eq!(q.dequeue(), Some(2));
             assert_eq!(q.dequeue(), Some(3));
@@ -372,27 +2682,132 @@ pub mod queue {
             assert_eq!(q.dequeue(), Some(6));
 
             let mut q = Queue::with_capacity(3);
-            q.enqueue(1);
-            q.enqueue(2);
+            q.enqueue(1).unwrap();
+            q.enqueue(2).unwrap();
             assert_eq!(q.dequeue(), Some(1));
             assert_eq!(q.dequeue(), Some(2));
-            q.enqueue(3);
-            q.enqueue(4);
-            q.enqueue(5); // resize here
-            q.enqueue(6);
+            q.enqueue(3).unwrap();
+            q.enqueue(4).unwrap();
+            q.enqueue(5).unwrap();
+            assert_eq!(q.capacity(), 3);
+            q.enqueue(6).unwrap(); // resize here
+            assert_eq!(q.capacity(), 6);
             assert_eq!(q.dequeue(), Some(3));
             assert_eq!(q.dequeue(), Some(4));
             assert_eq!(q.dequeue(), Some(5));
             assert_eq!(q.dequeue(), Some(6));
-            q.enqueue(7);
+            q.enqueue(7).unwrap();
             assert_eq!(q.dequeue(), Some(7));
         }
 
+        #[test]
+        fn find_map_test() {
+            let mut q = Queue::with_capacity(4);
+            q.enqueue('0').unwrap();
+            q.enqueue('0').unwrap();
+            q.enqueue('3').unwrap();
+            q.dequeue();
+            q.dequeue();
+            q.enqueue('x').unwrap();
+            q.enqueue('8').unwrap();
+            assert!(q.tail < q.head);
+
+            let digit_above =
+                |threshold| move |c: &char| c.to_digit(10).filter(|digit| *digit > threshold);
+            assert_eq!(q.find_map(digit_above(2)), Some(3));
+            assert_eq!(q.find_map(digit_above(5)), Some(8));
+            assert_eq!(q.find_map(digit_above(8)), None);
+            assert_eq!(q.len(), 3);
+        }
+
+        #[test]
+        fn sort_test() {
+            let mut q = Queue::with_capacity(4);
+            q.enqueue(0).unwrap();
+            q.enqueue(0).unwrap();
+            q.enqueue(3).unwrap();
+            q.dequeue();
+            q.dequeue();
+            q.enqueue(1).unwrap();
+            q.enqueue(2).unwrap();
+            assert!(q.tail < q.head);
+
+            q.sort();
+            assert_eq!(q.head, 0);
+            assert_eq!(q.tail, 3);
+            assert_eq!(q.cap, 4);
+            assert_eq!(q.dequeue(), Some(1));
+            assert_eq!(q.dequeue(), Some(2));
+            assert_eq!(q.dequeue(), Some(3));
+            assert_eq!(q.dequeue(), None);
+
+            // a full queue which wraps around
+            let mut q = Queue::bounded(3);
+            q.enqueue_all(vec![4, 3, 2]);
+            q.dequeue();
+            q.enqueue(1).unwrap();
+            q.sort();
+            assert_eq!(q.head, 0);
+            assert_eq!(q.tail, 0);
+            q.dequeue();
+            q.enqueue(4).unwrap();
+            assert_eq!(q.into_iter().collect::<Vec<i32>>(), vec![2, 3, 4]);
+        }
+
+        #[test]
+        fn sort_by_test() {
+            let mut q = Queue::new();
+            q.enqueue((1, 'a')).unwrap();
+            q.enqueue((0, 'b')).unwrap();
+            q.enqueue((1, 'c')).unwrap();
+            q.sort_by(|a, b| a.0.cmp(&b.0));
+            assert_eq!(q.dequeue(), Some((0, 'b')));
+            assert_eq!(q.dequeue(), Some((1, 'a')));
+            assert_eq!(q.dequeue(), Some((1, 'c')));
+        }
+
+        #[test]
+        fn into_sorted_vec_test() {
+            let mut q = Queue::with_capacity(3);
+            q.enqueue(0).unwrap();
+            q.enqueue(3).unwrap();
+            q.dequeue();
+            q.enqueue(1).unwrap();
+            q.enqueue(2).unwrap();
+            assert_eq!(q.into_sorted_vec(), vec![1, 2, 3]);
+
+            let q: Queue<i32> = Queue::new();
+            assert!(q.into_sorted_vec().is_empty());
+        }
+
+        #[test]
+        fn clear_test() {
+            let mut q = Queue::with_capacity(4);
+            for i in 0..3 {
+                q.enqueue(i.to_string()).unwrap();
+            }
+            q.dequeue();
+            q.enqueue(String::from("3")).unwrap();
+            assert!(q.tail < q.head);
+
+            q.clear();
+            assert!(q.empty());
+            assert_eq!(q.len(), 0);
+            assert_eq!(q.cap, 4);
+            assert!(q.list.iter().all(Option::is_none));
+
+            q.enqueue(String::from("a")).unwrap();
+            q.enqueue(String::from("b")).unwrap();
+            assert_eq!(q.dequeue(), Some(String::from("a")));
+            assert_eq!(q.dequeue(), Some(String::from("b")));
+            assert_eq!(q.dequeue(), None);
+        }
+
         #[test]
         fn empty_test() {
             let mut q = Queue::new();
             assert!(q.empty());
-            q.enqueue(1);
+            q.enqueue(1).unwrap();
             assert!(!q.empty());
             q.dequeue();
             assert!(q.empty());
@@ -402,21 +2817,297 @@ pub mod queue {
         fn len_test() {
             let mut q = Queue::new();
             assert_eq!(q.len(), 0);
-            q.enqueue(1);
+            q.enqueue(1).unwrap();
             assert_eq!(q.len(), 1);
             q.dequeue();
             assert_eq!(q.len(), 0);
             for i in 0..30 {
-                q.enqueue(i);
+                q.enqueue(i).unwrap();
             }
             for _ in 0..16 {
                 q.dequeue();
             }
             for i in 0..16 {
-                q.enqueue(i);
+                q.enqueue(i).unwrap();
             }
             assert!(q.tail < q.head);
             assert_eq!(q.len(), 30);
         }
+
+        #[test]
+        fn capacity_test() {
+            let mut q = Queue::with_capacity(4);
+            assert_eq!(q.capacity(), 4);
+            for i in 0..5 {
+                q.enqueue(i).unwrap();
+            }
+            assert_eq!(q.capacity(), 8);
+            assert_eq!(Queue::<i32>::new().capacity(), DEFAULT_INIT_QUEUE_CAPACITY);
+        }
+
+        #[test]
+        fn reserve_test() {
+            let mut q = Queue::with_capacity(4);
+            q.reserve(4);
+            assert_eq!(q.capacity(), 4);
+
+            for i in 0..3 {
+                q.enqueue(i).unwrap();
+            }
+            q.dequeue();
+            q.dequeue();
+            q.enqueue(3).unwrap();
+            q.enqueue(4).unwrap();
+            assert!(q.tail < q.head);
+
+            q.reserve(1);
+            assert_eq!(q.capacity(), 4);
+            q.reserve(10);
+            assert_eq!(q.capacity(), 13);
+            assert_eq!(q.head, 0);
+            assert_eq!(q.tail, 3);
+            assert_eq!(q.refs(), vec![&2, &3, &4]);
+            for i in 5..15 {
+                q.enqueue(i).unwrap();
+            }
+            assert_eq!(q.capacity(), 13);
+            assert_eq!(q.dequeue(), Some(2));
+        }
+
+        #[test]
+        fn shrink_to_fit_test() {
+            let mut q: Queue<i32> = (0..30).collect();
+            q.enqueue(30).unwrap();
+            assert_eq!(q.capacity(), 60);
+            // drain most of the queue, and wrap it around
+            for _ in 0..27 {
+                q.dequeue();
+            }
+            for i in 31..60 {
+                q.enqueue(i).unwrap();
+                q.dequeue();
+            }
+            assert!(q.tail < q.head);
+
+            q.shrink_to_fit();
+            assert_eq!(q.capacity(), 4);
+            assert_eq!(q.head, 0);
+            assert_eq!(q.tail, 0);
+            q.shrink_to_fit();
+            assert_eq!(q.capacity(), 4);
+            assert_eq!(q.refs(), vec![&56, &57, &58, &59]);
+
+            q.clear();
+            q.shrink_to_fit();
+            assert_eq!(q.capacity(), 0);
+            q.enqueue(1).unwrap();
+            assert_eq!(q.dequeue(), Some(1));
+        }
+
+        #[test]
+        fn load_factor_test() {
+            let mut q = Queue::with_capacity(4);
+            assert_eq!(q.load_factor(), 0.0);
+            q.enqueue(1).unwrap();
+            assert_eq!(q.load_factor(), 0.25);
+            q.enqueue(2).unwrap();
+            q.enqueue(3).unwrap();
+            assert_eq!(q.load_factor(), 0.75);
+            q.dequeue();
+            assert_eq!(q.load_factor(), 0.5);
+
+            let q: Queue<i32> = Queue::with_capacity(0);
+            assert_eq!(q.load_factor(), 0.0);
+        }
+
+        #[test]
+        fn peek_range_test() {
+            let mut q = Queue::with_capacity(4);
+            q.enqueue(1).unwrap();
+            q.enqueue(2).unwrap();
+            q.enqueue(3).unwrap();
+            q.dequeue();
+            q.dequeue();
+            q.enqueue(4).unwrap();
+            q.enqueue(5).unwrap();
+            assert!(q.tail < q.head);
+
+            assert_eq!(q.peek_range(0, 3), vec![3, 4, 5]);
+            assert_eq!(q.peek_range(1, 2), vec![4, 5]);
+            assert_eq!(q.peek_range(1, 10), vec![4, 5]);
+            assert!(q.peek_range(3, 1).is_empty());
+            assert_eq!(q.len(), 3);
+            assert_eq!(q.dequeue(), Some(3));
+        }
+
+        #[test]
+        fn refs_test() {
+            let q: Queue<i32> = Queue::new();
+            assert!(q.refs().is_empty());
+
+            let mut q = Queue::with_capacity(4);
+            for i in 0..3 {
+                q.enqueue(i).unwrap();
+            }
+            q.dequeue();
+            q.dequeue();
+            for i in 3..5 {
+                q.enqueue(i).unwrap();
+            }
+            assert!(q.tail < q.head);
+
+            assert_eq!(q.refs(), vec![&2, &3, &4]);
+            assert_eq!(q.len(), 3);
+            assert_eq!(q.dequeue(), Some(2));
+            assert_eq!(q.dequeue(), Some(3));
+            assert_eq!(q.dequeue(), Some(4));
+        }
+
+        #[test]
+        fn iter_test() {
+            let mut q = Queue::with_capacity(4);
+            for i in 0..3 {
+                q.enqueue(i).unwrap();
+            }
+            q.dequeue();
+            q.dequeue();
+            q.enqueue(3).unwrap();
+            q.enqueue(4).unwrap();
+            assert!(q.tail < q.head);
+
+            let items: Vec<i32> = q.iter().copied().collect();
+            assert_eq!(items, vec![2, 3, 4]);
+            assert_eq!(q.len(), 3);
+
+            let mut dequeued = Vec::new();
+            while let Some(i) = q.dequeue() {
+                dequeued.push(i);
+            }
+            assert_eq!(items, dequeued);
+        }
+
+        #[test]
+        fn into_iter_test() {
+            let mut q = Queue::with_capacity(4);
+            for word in ["a", "b", "c"] {
+                q.enqueue(word.to_string()).unwrap();
+            }
+            q.dequeue();
+            q.dequeue();
+            q.enqueue(String::from("d")).unwrap();
+            q.enqueue(String::from("e")).unwrap();
+            assert!(q.tail < q.head);
+
+            let iter = q.into_iter();
+            assert_eq!(iter.size_hint(), (3, Some(3)));
+            let items: Vec<String> = iter.collect();
+            assert_eq!(items, vec!["c", "d", "e"]);
+
+            let q: Queue<i32> = Queue::new();
+            assert_eq!(q.into_iter().next(), None);
+        }
+
+        #[test]
+        fn debug_test() {
+            let mut q = Queue::with_capacity(3);
+            assert_eq!(format!("{:?}", q), "[]");
+            q.enqueue(0).unwrap();
+            q.enqueue(1).unwrap();
+            q.enqueue(2).unwrap();
+            assert_eq!(format!("{:?}", q), "[0, 1, 2]");
+
+            // wrap the items around the end of the internal list
+            q.dequeue();
+            q.dequeue();
+            q.enqueue(3).unwrap();
+            q.enqueue(4).unwrap();
+            assert_eq!(q.head, 2);
+            assert_eq!(format!("{:?}", q), "[2, 3, 4]");
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn serde_test() {
+            // wrap the items around the end of the internal list
+            let mut q = Queue::with_capacity(3);
+            q.enqueue(0).unwrap();
+            q.enqueue(1).unwrap();
+            q.enqueue(2).unwrap();
+            q.dequeue();
+            q.enqueue(3).unwrap();
+            let json = serde_json::to_string(&q).unwrap();
+            assert_eq!(json, "[1,2,3]");
+            let mut back: Queue<i32> = serde_json::from_str(&json).unwrap();
+            assert_eq!(back.dequeue(), Some(1));
+            assert_eq!(back.dequeue(), Some(2));
+            assert_eq!(back.dequeue(), Some(3));
+            assert_eq!(back.dequeue(), None);
+
+            let empty: Queue<String> = Queue::new();
+            let json = serde_json::to_string(&empty).unwrap();
+            assert_eq!(json, "[]");
+            let back: Queue<String> = serde_json::from_str(&json).unwrap();
+            assert!(back.empty());
+        }
+
+        #[test]
+        fn clone_test() {
+            let mut q = Queue::with_capacity(4);
+            for i in 0..4 {
+                q.enqueue(i).unwrap();
+            }
+            q.dequeue();
+            q.dequeue();
+            q.enqueue(4).unwrap();
+            assert_eq!(q.head, 2);
+            assert_eq!(q.tail, 1);
+
+            let mut clone = q.clone();
+            assert_eq!(clone.head, 0);
+            assert_eq!(clone.tail, 3);
+            assert_eq!(clone.capacity(), 4);
+            assert_eq!(clone.list[3], None);
+
+            q.dequeue();
+            q.enqueue(5).unwrap();
+            q.enqueue(6).unwrap();
+            assert_eq!(clone.len(), 3);
+            assert_eq!(clone.dequeue_with_age(), Some((2, 2)));
+            assert_eq!(clone.dequeue(), Some(3));
+            assert_eq!(clone.dequeue(), Some(4));
+            assert_eq!(clone.dequeue(), None);
+            assert_eq!(q.refs(), vec![&3, &4, &5, &6]);
+
+            // a full queue
+            let mut q = Queue::bounded(2);
+            q.enqueue(String::from("a")).unwrap();
+            q.enqueue(String::from("b")).unwrap();
+            let mut clone = q.clone();
+            assert_eq!(clone.tail, 0);
+            assert!(clone.enqueue(String::from("c")).is_err());
+            assert_eq!(clone.dequeue().as_deref(), Some("a"));
+            assert_eq!(q.len(), 2);
+        }
+
+        #[test]
+        fn iter_from_test() {
+            let mut q = Queue::with_capacity(6);
+            for i in 0..4 {
+                q.enqueue(i).unwrap();
+            }
+            q.dequeue();
+            q.dequeue();
+            for i in 4..7 {
+                q.enqueue(i).unwrap();
+            }
+            assert!(q.tail < q.head);
+            assert_eq!(q.len(), 5);
+
+            let rest: Vec<i32> = q.iter_from(2).copied().collect();
+            assert_eq!(rest, vec![4, 5, 6]);
+            assert_eq!(q.iter_from(5).count(), 0);
+            assert_eq!(q.iter_from(10).count(), 0);
+            assert_eq!(q.len(), 5);
+        }
     }
 } /* queue */